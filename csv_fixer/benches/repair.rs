@@ -0,0 +1,27 @@
+use csv_fixer::bench::benchmark_repair;
+use csv_fixer::generate::{ColumnSpec, FieldKind, generate_test_csv};
+
+const ITERATIONS: usize = 20;
+
+// Run with `cargo bench --features bench`
+fn main() {
+    let specs = [
+        ColumnSpec::new("id", FieldKind::Uuid),
+        ColumnSpec::new("name", FieldKind::String),
+        ColumnSpec::new("email", FieldKind::Email),
+        ColumnSpec::new("amount", FieldKind::Float),
+        ColumnSpec::new("created", FieldKind::Date),
+    ];
+    for rows in [1_000, 10_000, 100_000] {
+        let csv = generate_test_csv(&specs, rows, 42).expect("valid column specs");
+        let result = benchmark_repair(csv.as_bytes(), ITERATIONS);
+        println!(
+            "repair {rows:>7} rows ({:>9} bytes): mean {:.3} ms, min {:.3} ms, max {:.3} ms, {:.1} MB/s",
+            csv.len(),
+            result.mean_ms,
+            result.min_ms,
+            result.max_ms,
+            result.throughput_mbps
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Instant;
+
+use crate::context::CsvContext;
+use crate::repair::repair;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkResult {
+    pub mean_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub throughput_mbps: f64,
+}
+
+// Time the repair steps over `data` for `iterations` runs
+pub fn benchmark_repair(data: &[u8], iterations: usize) -> BenchmarkResult {
+    let iterations = iterations.max(1);
+    let mut timings = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let mut ctx = CsvContext::new(data.to_vec());
+        repair(&mut ctx);
+        timings.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+
+    let total_ms: f64 = timings.iter().sum();
+    let mean_ms = total_ms / iterations as f64;
+    let megabytes = data.len() as f64 / (1024.0 * 1024.0);
+    BenchmarkResult {
+        mean_ms,
+        min_ms: timings.iter().copied().fold(f64::INFINITY, f64::min),
+        max_ms: timings.iter().copied().fold(0.0, f64::max),
+        throughput_mbps: if mean_ms > 0.0 {
+            megabytes / (mean_ms / 1000.0)
+        } else {
+            0.0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn benchmark_reports_ordered_timings() {
+        let result = benchmark_repair(b"a,b\n\"1\r\n2\",3\n", 5);
+        assert!(result.min_ms <= result.mean_ms && result.mean_ms <= result.max_ms);
+        assert!(result.throughput_mbps >= 0.0);
+    }
+}
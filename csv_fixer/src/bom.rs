@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::context::CsvContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Bom {
+    // UTF-32 LE starts with the UTF-16 LE mark, so it has to be checked first
+    const SIGNATURES: [(Bom, &'static [u8]); 5] = [
+        (Bom::Utf32Le, &[0xFF, 0xFE, 0x00, 0x00]),
+        (Bom::Utf32Be, &[0x00, 0x00, 0xFE, 0xFF]),
+        (Bom::Utf8, &[0xEF, 0xBB, 0xBF]),
+        (Bom::Utf16Le, &[0xFF, 0xFE]),
+        (Bom::Utf16Be, &[0xFE, 0xFF]),
+    ];
+
+    pub fn detect(bytes: &[u8]) -> Option<Bom> {
+        Self::SIGNATURES
+            .iter()
+            .find(|(_, signature)| bytes.starts_with(signature))
+            .map(|(bom, _)| *bom)
+    }
+
+    pub fn byte_length(self) -> usize {
+        match self {
+            Bom::Utf8 => 3,
+            Bom::Utf16Le | Bom::Utf16Be => 2,
+            Bom::Utf32Le | Bom::Utf32Be => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BomFound {
+    pub bom: Bom,
+    pub byte_length: usize,
+}
+
+impl fmt::Display for BomFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} BOM found ({} bytes)", self.bom, self.byte_length)
+    }
+}
+
+impl Error for BomFound {}
+
+// Fail if `raw_data` starts with a byte order mark. Nothing is modified.
+pub fn assert_no_bom(ctx: &CsvContext) -> Result<(), BomFound> {
+    match Bom::detect(&ctx.raw_data) {
+        Some(bom) => Err(BomFound {
+            bom,
+            byte_length: bom.byte_length(),
+        }),
+        None => Ok(()),
+    }
+}
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmbeddedBomReport {
+    pub removed: usize,
+    // Offsets into the original `raw_data`
+    pub at_offsets: Vec<usize>,
+}
+
+// `text` without U+FEFF past its first character, and the number removed.
+// Shared with the builder step so both remove the same marks.
+pub(crate) fn strip_embedded_boms(text: &str) -> (String, usize) {
+    let first_len = text.chars().next().map_or(0, char::len_utf8);
+    let (first, rest) = text.split_at(first_len);
+    let removed = rest.matches('\u{feff}').count();
+    if removed == 0 {
+        return (text.to_string(), 0);
+    }
+    (format!("{first}{}", rest.replace('\u{feff}', "")), removed)
+}
+
+// Remove UTF-8 BOMs found past the start of `raw_data`, as left behind by
+// naive concatenation of files. A leading BOM is kept. `processed_text` is
+// cleaned the same way so both copies stay in sync.
+pub fn remove_embedded_boms(ctx: &mut CsvContext) -> EmbeddedBomReport {
+    let mut report = EmbeddedBomReport::default();
+    let mut cleaned = Vec::with_capacity(ctx.raw_data.len());
+    let mut i = 0;
+
+    while i < ctx.raw_data.len() {
+        if i > 0 && ctx.raw_data[i..].starts_with(&UTF8_BOM) {
+            report.removed += 1;
+            report.at_offsets.push(i);
+            i += UTF8_BOM.len();
+            continue;
+        }
+        cleaned.push(ctx.raw_data[i]);
+        i += 1;
+    }
+
+    ctx.raw_data = cleaned;
+    if ctx.processed_text.contains('\u{feff}') {
+        ctx.processed_text = strip_embedded_boms(&ctx.processed_text).0;
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bom_is_reported_with_length() {
+        let ctx = CsvContext::new(vec![0xFF, 0xFE, 0x00, 0x00, b'a']);
+        assert_eq!(
+            assert_no_bom(&ctx),
+            Err(BomFound {
+                bom: Bom::Utf32Le,
+                byte_length: 4,
+            })
+        );
+        assert_eq!(assert_no_bom(&CsvContext::new(b"a,b\n".to_vec())), Ok(()));
+    }
+
+    #[test]
+    fn embedded_boms_are_removed() {
+        let mut data = vec![0xEF, 0xBB, 0xBF];
+        data.extend_from_slice(b"a\n");
+        data.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+        data.extend_from_slice(b"b\n");
+        let mut ctx = CsvContext::new(data);
+
+        let report = remove_embedded_boms(&mut ctx);
+        assert_eq!(report.removed, 1);
+        assert_eq!(report.at_offsets, vec![5]);
+        assert_eq!(ctx.raw_data, b"\xEF\xBB\xBFa\nb\n");
+        assert_eq!(ctx.processed_text, "\u{feff}a\nb\n");
+    }
+}
@@ -0,0 +1,211 @@
+use crate::bom::{self, Bom};
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+use crate::repair::{self, RepairReport};
+
+// Fluent construction of a repair pipeline. Each step runs immediately on the
+// wrapped context and records an event in the report when it changes anything.
+//
+//     let (text, report) = CsvRepairBuilder::new(bytes)
+//         .strip_bom()
+//         .normalize_line_endings()
+//         .filter_control_chars()
+//         .trim_fields(b',')
+//         .build();
+#[derive(Debug, Clone)]
+pub struct CsvRepairBuilder {
+    ctx: CsvContext,
+    report: RepairReport,
+}
+
+impl CsvRepairBuilder {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self::from_context(CsvContext::new(bytes))
+    }
+
+    pub fn from_context(ctx: CsvContext) -> Self {
+        let report = RepairReport {
+            bytes_in: ctx.raw_data.len(),
+            ..RepairReport::default()
+        };
+        Self { ctx, report }
+    }
+
+    fn record(&mut self, count: usize, event: &str) {
+        if count > 0 {
+            self.report.events.push(format!("{event}: {count}"));
+        }
+    }
+
+    // Drop a leading byte order mark from the decoded text. Nothing is
+    // recorded when an earlier step or the decoder already removed it.
+    pub fn strip_bom(mut self) -> Self {
+        if let Some(rest) = self.ctx.processed_text.strip_prefix('\u{feff}') {
+            self.ctx.processed_text = rest.to_string();
+            let found = Bom::detect(&self.ctx.raw_data).unwrap_or(Bom::Utf8);
+            self.record(
+                found.byte_length(),
+                &format!("stripped {found:?} BOM bytes"),
+            );
+        }
+        self
+    }
+
+    // Remove BOMs left in the middle of the data by concatenation. A leading
+    // BOM is kept; see `strip_bom`.
+    pub fn remove_embedded_boms(mut self) -> Self {
+        let (text, removed) = bom::strip_embedded_boms(&self.ctx.processed_text);
+        if removed > 0 {
+            self.ctx.processed_text = text;
+        }
+        self.record(removed, "removed embedded BOMs");
+        self
+    }
+
+    // Remove `\0` characters, keeping the result of earlier steps
+    pub fn remove_null_bytes(mut self) -> Self {
+        let removed = self.ctx.processed_text.matches('\0').count();
+        if removed > 0 {
+            self.ctx.processed_text = self.ctx.processed_text.replace('\0', "");
+        }
+        self.record(removed, "removed null bytes");
+        self
+    }
+
+    // Convert `\r\n` and lone `\r` to `\n`
+    pub fn normalize_line_endings(mut self) -> Self {
+        let text = &self.ctx.processed_text;
+        let count = text.matches('\r').count();
+        if count > 0 {
+            self.ctx.processed_text = text.replace("\r\n", "\n").replace('\r', "\n");
+        }
+        self.record(count, "normalized line endings");
+        self
+    }
+
+    pub fn normalize_crlf_in_quoted(mut self, delimiter: u8, substitute: &str) -> Self {
+        let count = repair::normalize_crlf_in_quoted(&mut self.ctx, delimiter, substitute);
+        self.record(count, "replaced line breaks in quoted fields");
+        self
+    }
+
+    // Remove control characters other than tab and line breaks, including `\0`
+    pub fn filter_control_chars(mut self) -> Self {
+        let keep = |c: &char| !c.is_control() || matches!(c, '\t' | '\n' | '\r');
+        let before = self.ctx.processed_text.chars().count();
+        self.ctx.processed_text = self.ctx.processed_text.chars().filter(keep).collect();
+        let removed = before - self.ctx.processed_text.chars().count();
+        self.record(removed, "removed control characters");
+        self
+    }
+
+    // Trim leading and trailing whitespace from every field
+    pub fn trim_fields(mut self, delimiter: u8) -> Self {
+        let mut rows = match read_rows(&self.ctx.processed_text, delimiter) {
+            Ok(rows) => rows,
+            Err(err) => {
+                self.report.skipped.push(format!("trim fields: {err}"));
+                return self;
+            }
+        };
+        let mut trimmed = 0;
+        for field in rows.iter_mut().flatten() {
+            if field.trim() != field {
+                *field = field.trim().to_string();
+                trimmed += 1;
+            }
+        }
+        if trimmed > 0 {
+            match write_rows(&rows, delimiter) {
+                Ok(text) => self.ctx.processed_text = text,
+                Err(err) => {
+                    self.report.skipped.push(format!("trim fields: {err}"));
+                    return self;
+                }
+            }
+        }
+        self.record(trimmed, "trimmed fields");
+        self
+    }
+
+    pub fn build_context(mut self) -> (CsvContext, RepairReport) {
+        self.report.bytes_out = self.ctx.processed_text.len();
+        (self.ctx, self.report)
+    }
+
+    pub fn build(self) -> (String, RepairReport) {
+        let (ctx, report) = self.build_context();
+        (ctx.processed_text, report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_bom_removes_leading_mark() {
+        let (text, report) = CsvRepairBuilder::new(b"\xEF\xBB\xBFa,b\n".to_vec())
+            .strip_bom()
+            .build();
+        assert_eq!(text, "a,b\n");
+        assert_eq!(report.events, ["stripped Utf8 BOM bytes: 3"]);
+
+        let (text, report) = CsvRepairBuilder::new(b"\xEF\xBB\xBFa,b\n".to_vec())
+            .strip_bom()
+            .strip_bom()
+            .build();
+        assert_eq!(text, "a,b\n");
+        assert_eq!(report.events.len(), 1);
+    }
+
+    #[test]
+    fn embedded_boms_are_removed_after_earlier_steps() {
+        let (text, report) =
+            CsvRepairBuilder::new(b"\xEF\xBB\xBFa , b\n\xEF\xBB\xBF1 ,2\n".to_vec())
+                .trim_fields(b',')
+                .remove_embedded_boms()
+                .build();
+        assert_eq!(text, "a,b\n1,2\n");
+        assert_eq!(report.events.last().unwrap(), "removed embedded BOMs: 1");
+    }
+
+    #[test]
+    fn remove_null_bytes_keeps_earlier_steps() {
+        let (text, report) = CsvRepairBuilder::new(b"a\r\n1\0\r\n".to_vec())
+            .normalize_line_endings()
+            .remove_null_bytes()
+            .build();
+        assert_eq!(text, "a\n1\n");
+        assert_eq!(report.events.last().unwrap(), "removed null bytes: 1");
+    }
+
+    #[test]
+    fn normalize_line_endings_converts_cr() {
+        let (text, _) = CsvRepairBuilder::new(b"a\r\nb\rc\n".to_vec())
+            .normalize_line_endings()
+            .build();
+        assert_eq!(text, "a\nb\nc\n");
+    }
+
+    #[test]
+    fn filter_control_chars_keeps_whitespace() {
+        let (text, _) = CsvRepairBuilder::new(b"a\0b\x07\tc\n".to_vec())
+            .filter_control_chars()
+            .build();
+        assert_eq!(text, "ab\tc\n");
+    }
+
+    #[test]
+    fn full_pipeline_reports_each_step() {
+        let (text, report) = CsvRepairBuilder::new(b"\xEF\xBB\xBF a ,b\r\n1\0, 2 \r\n".to_vec())
+            .strip_bom()
+            .normalize_line_endings()
+            .filter_control_chars()
+            .trim_fields(b',')
+            .build();
+        assert_eq!(text, "a,b\n1,2\n");
+        assert_eq!(report.events.len(), 4);
+        assert_eq!(report.bytes_out, text.len());
+    }
+}
@@ -0,0 +1,235 @@
+use std::error::Error;
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Crc32,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    // Lowercase hex digest of `bytes`
+    pub fn digest(self, bytes: &[u8]) -> String {
+        match self {
+            ChecksumAlgorithm::Crc32 => format!("{:08x}", crc32(bytes)),
+            ChecksumAlgorithm::Sha256 => sha256(bytes).iter().map(|b| format!("{b:02x}")).collect(),
+        }
+    }
+}
+
+// IEEE 802.3 CRC-32, bitwise; rows are short enough that a table isn't worth it
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// FIPS 180-4 SHA-256
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (chunk, word) in out.chunks_exact_mut(4).zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+// Field values joined with `\0` so `a,bc` and `ab,c` hash differently
+fn row_digest<'a>(
+    fields: impl Iterator<Item = &'a String>,
+    algorithm: ChecksumAlgorithm,
+) -> String {
+    let joined: Vec<&str> = fields.map(String::as_str).collect();
+    algorithm.digest(joined.join("\0").as_bytes())
+}
+
+// Append a column named `column_name` holding the checksum of each data row.
+// Short rows are padded to the header width first so the checksum lines up
+// with its header; fields past the header stay after it and are hashed too.
+pub fn add_row_checksum(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    algorithm: ChecksumAlgorithm,
+    column_name: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first_mut() else {
+        return Ok(());
+    };
+    let width = header.len();
+    header.push(column_name.to_string());
+    for row in data.iter_mut() {
+        if row.len() < width {
+            row.resize(width, String::new());
+        }
+        let digest = row_digest(row.iter(), algorithm);
+        row.insert(width, digest);
+    }
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub row: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+// Recompute each data row's checksum without `checksum_column` and compare it
+// with the stored value. An empty result means every row matched.
+pub fn verify_row_checksums(
+    ctx: &CsvContext,
+    delimiter: u8,
+    checksum_column: &Column,
+    algorithm: ChecksumAlgorithm,
+) -> Result<Vec<ChecksumMismatch>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let index = checksum_column.resolve(header)?;
+
+    let mut mismatches = Vec::new();
+    for (offset, row) in data.iter().enumerate() {
+        let expected = row.get(index).cloned().unwrap_or_default();
+        let fields = row
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != index)
+            .map(|(_, value)| value);
+        let actual = row_digest(fields, algorithm);
+        if !expected.eq_ignore_ascii_case(&actual) {
+            mismatches.push(ChecksumMismatch {
+                row: offset + 1,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn digests_match_known_vectors() {
+        assert_eq!(ChecksumAlgorithm::Crc32.digest(b"123456789"), "cbf43926");
+        assert_eq!(
+            ChecksumAlgorithm::Sha256.digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn checksum_column_is_appended() {
+        let mut c = ctx("a,b\n1,2\n");
+        add_row_checksum(&mut c, b',', ChecksumAlgorithm::Crc32, "crc").unwrap();
+        let expected = ChecksumAlgorithm::Crc32.digest(b"1\x002");
+        assert_eq!(c.processed_text, format!("a,b,crc\n1,2,{expected}\n"));
+    }
+
+    #[test]
+    fn checksums_are_verified() {
+        let mut c = ctx("a,b\n1,2\n3,4\n");
+        add_row_checksum(&mut c, b',', ChecksumAlgorithm::Sha256, "sha").unwrap();
+        let column = Column::from("sha");
+        let ok = verify_row_checksums(&c, b',', &column, ChecksumAlgorithm::Sha256).unwrap();
+        assert!(ok.is_empty());
+
+        c.processed_text = c.processed_text.replacen("3,4", "3,5", 1);
+        let bad = verify_row_checksums(&c, b',', &column, ChecksumAlgorithm::Sha256).unwrap();
+        assert_eq!(bad.len(), 1);
+        assert_eq!(bad[0].row, 2);
+        assert_eq!(bad[0].actual, ChecksumAlgorithm::Sha256.digest(b"3\x005"));
+    }
+
+    #[test]
+    fn ragged_rows_round_trip() {
+        let mut c = ctx("a,b,c\n1\n2,3,4,5\n");
+        add_row_checksum(&mut c, b',', ChecksumAlgorithm::Crc32, "crc").unwrap();
+        let rows = read_rows(&c.processed_text, b',').unwrap();
+        assert_eq!(rows[1][3], ChecksumAlgorithm::Crc32.digest(b"1\0\0"));
+        assert_eq!(rows[2][4], "5");
+        let column = Column::from("crc");
+        let result = verify_row_checksums(&c, b',', &column, ChecksumAlgorithm::Crc32).unwrap();
+        assert!(result.is_empty());
+    }
+}
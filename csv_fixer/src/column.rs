@@ -0,0 +1,49 @@
+use std::error::Error;
+
+// Columns can be addressed by position or by header name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl Column {
+    pub fn resolve(&self, header: &[String]) -> Result<usize, Box<dyn Error>> {
+        match self {
+            Column::Index(index) if *index < header.len() => Ok(*index),
+            Column::Index(index) => Err(format!("column index {index} out of range").into()),
+            Column::Name(name) => header
+                .iter()
+                .position(|h| h == name)
+                .ok_or_else(|| format!("column not found: {name}").into()),
+        }
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+// A set of columns: every column, or an explicit list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnSelection {
+    All,
+    Only(Vec<Column>),
+}
+
+impl ColumnSelection {
+    pub fn resolve(&self, header: &[String]) -> Result<Vec<usize>, Box<dyn Error>> {
+        match self {
+            ColumnSelection::All => Ok((0..header.len()).collect()),
+            ColumnSelection::Only(columns) => columns.iter().map(|c| c.resolve(header)).collect(),
+        }
+    }
+}
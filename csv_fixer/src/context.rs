@@ -0,0 +1,26 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Working state for one CSV file:
+// - raw_data: bytes exactly as they were read
+// - processed_text: the decoded text that repair steps operate on
+#[derive(Debug, Clone, Default)]
+pub struct CsvContext {
+    pub raw_data: Vec<u8>,
+    pub processed_text: String,
+}
+
+impl CsvContext {
+    pub fn new(raw_data: Vec<u8>) -> Self {
+        let processed_text = String::from_utf8_lossy(&raw_data).into_owned();
+        Self {
+            raw_data,
+            processed_text,
+        }
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(fs::read(path)?))
+    }
+}
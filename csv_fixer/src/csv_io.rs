@@ -0,0 +1,176 @@
+use std::error::Error;
+
+// Parse text into rows of owned fields. Rows are allowed to differ in length
+// so that callers can inspect or repair ragged files.
+pub fn read_rows(text: &str, delimiter: u8) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(text.as_bytes());
+
+    let mut rows = Vec::new();
+    for result in rdr.records() {
+        let record = result?;
+        rows.push(record.iter().map(String::from).collect());
+    }
+    Ok(rows)
+}
+
+// Serialize rows back to text using minimal quoting and `\n` line endings
+pub fn write_rows(rows: &[Vec<String>], delimiter: u8) -> Result<String, Box<dyn Error>> {
+    write_rows_with_quoting(rows, delimiter, csv::QuoteStyle::Necessary)
+}
+
+pub fn write_rows_with_quoting(
+    rows: &[Vec<String>],
+    delimiter: u8,
+    quote_style: csv::QuoteStyle,
+) -> Result<String, Box<dyn Error>> {
+    let mut wtr = csv::WriterBuilder::new()
+        .flexible(true)
+        .quote_style(quote_style)
+        .delimiter(delimiter)
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+
+    for row in rows {
+        wtr.write_record(row)?;
+    }
+    let bytes = wtr.into_inner().map_err(|err| err.to_string())?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+// Split text into logical records, each keeping its line terminator. Line
+// breaks inside quoted fields do not end a record.
+pub fn split_records(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut records = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => in_quotes = !in_quotes,
+            b'\r' | b'\n' if !in_quotes => {
+                if bytes[i] == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+                    i += 1;
+                }
+                records.push(&text[start..=i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if start < bytes.len() {
+        records.push(&text[start..]);
+    }
+    records
+}
+
+// A record without its trailing `\n`, `\r\n`, or `\r`
+pub fn trim_terminator(record: &str) -> &str {
+    record.trim_end_matches(['\r', '\n'])
+}
+
+// A field as it appeared in the source text, before quoting is discarded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawField {
+    pub value: String,
+    pub quoted: bool,
+}
+
+impl RawField {
+    pub fn needs_quotes(&self, delimiter: u8) -> bool {
+        needs_quotes(&self.value, delimiter)
+    }
+}
+
+// Quoting is mandatory when the value holds a delimiter, quote, or line break
+pub fn needs_quotes(value: &str, delimiter: u8) -> bool {
+    value
+        .chars()
+        .any(|c| c == delimiter as char || c == '"' || c == '\n' || c == '\r')
+}
+
+// Tokenize text into rows of fields while remembering which fields were quoted.
+// Quoted fields may span lines; empty lines are skipped like the csv reader does.
+pub fn scan_fields(text: &str, delimiter: u8) -> Vec<Vec<RawField>> {
+    let delimiter = delimiter as char;
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = RawField::default();
+    let mut in_quotes = false;
+    let mut at_field_start = true;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.value.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.value.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            '"' if at_field_start => {
+                field.quoted = true;
+                in_quotes = true;
+                at_field_start = false;
+            }
+            c if c == delimiter => {
+                row.push(std::mem::take(&mut field));
+                at_field_start = true;
+            }
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                if !row.is_empty() || !field.value.is_empty() || field.quoted {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                at_field_start = true;
+            }
+            _ => {
+                field.value.push(c);
+                at_field_start = false;
+            }
+        }
+    }
+    if !row.is_empty() || !field.value.is_empty() || field.quoted {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_fields_tracks_quoting() {
+        let rows = scan_fields("\"a\",b\r\n\"x\"\"y\",\"1\n2\"\n\n", b',');
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0][0].quoted && !rows[0][1].quoted);
+        assert_eq!(rows[1][0].value, "x\"y");
+        assert_eq!(rows[1][1].value, "1\n2");
+    }
+
+    #[test]
+    fn split_records_respects_quotes() {
+        let records = split_records("a,b\r\n\"1\n2\",3\n\nlast");
+        assert_eq!(records, vec!["a,b\r\n", "\"1\n2\",3\n", "\n", "last"]);
+        assert_eq!(trim_terminator(records[0]), "a,b");
+    }
+}
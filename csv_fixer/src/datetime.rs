@@ -0,0 +1,668 @@
+use std::error::Error;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+use crate::transform::map_column;
+use crate::validate::{column_values, is_null_value};
+
+// A strftime subset covering the formats seen in CSV exports: %Y %y %m %d %H
+// %M %S %f %.f %z %:z %b %F %T and %%. Date-only formats parse as midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spec {
+    Year,
+    ShortYear,
+    Month,
+    MonthName,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Nanos,
+    DotNanos,
+    Offset,
+    ColonOffset,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    Spec(Spec),
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    // Seconds east of UTC; None for naive timestamps
+    offset: Option<i32>,
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar (H. Hinnant)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    // Local time at `offset` seconds east of UTC
+    pub(crate) fn from_epoch_nanos(nanos: i128, offset: i32) -> Self {
+        let local = nanos + i128::from(offset) * 1_000_000_000;
+        let seconds = local.div_euclid(1_000_000_000) as i64;
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let time = seconds.rem_euclid(86_400) as u32;
+        DateTime {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+            nanosecond: local.rem_euclid(1_000_000_000) as u32,
+            offset: Some(offset),
+        }
+    }
+
+    // Nanoseconds since the epoch; naive timestamps are taken as UTC
+    pub(crate) fn epoch_nanos(&self) -> i128 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let seconds = days * 86_400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+            - i64::from(self.offset.unwrap_or(0));
+        i128::from(seconds) * 1_000_000_000 + i128::from(self.nanosecond)
+    }
+}
+
+// Take between one and `max` ASCII digits
+fn take_digits(input: &mut &str, max: usize) -> Option<u32> {
+    let len = input
+        .bytes()
+        .take(max)
+        .take_while(u8::is_ascii_digit)
+        .count();
+    if len == 0 {
+        return None;
+    }
+    let (digits, rest) = input.split_at(len);
+    *input = rest;
+    digits.parse().ok()
+}
+
+// `Z`, `+hh`, `+hhmm`, or `+hh:mm`
+fn take_offset(input: &mut &str) -> Option<i32> {
+    if let Some(rest) = input.strip_prefix(['Z', 'z']) {
+        *input = rest;
+        return Some(0);
+    }
+    let sign = match input.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    *input = &input[1..];
+    let hours = take_digits(input, 2)?;
+    if let Some(rest) = input.strip_prefix(':') {
+        *input = rest;
+    }
+    let minutes = take_digits(input, 2).unwrap_or(0);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 3600 + minutes * 60) as i32)
+}
+
+fn take_nanos(input: &mut &str) -> Option<u32> {
+    let before = input.len();
+    let digits = take_digits(input, 9)?;
+    let len = before - input.len();
+    // Ignore precision past nanoseconds
+    while input.starts_with(|c: char| c.is_ascii_digit()) {
+        *input = &input[1..];
+    }
+    Some(digits * 10u32.pow(9 - len as u32))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Format(Vec<Token>);
+
+impl Format {
+    pub(crate) fn new(format: &str) -> Result<Self, Box<dyn Error>> {
+        let mut tokens = Vec::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                tokens.push(Token::Literal(c));
+                continue;
+            }
+            let spec = match chars.next() {
+                Some('Y') => Spec::Year,
+                Some('y') => Spec::ShortYear,
+                Some('m') => Spec::Month,
+                Some('b') => Spec::MonthName,
+                Some('d') => Spec::Day,
+                Some('H') => Spec::Hour,
+                Some('M') => Spec::Minute,
+                Some('S') => Spec::Second,
+                Some('f') => Spec::Nanos,
+                Some('z') => Spec::Offset,
+                Some('%') => {
+                    tokens.push(Token::Literal('%'));
+                    continue;
+                }
+                Some('F') => {
+                    tokens.extend(Self::new("%Y-%m-%d")?.0);
+                    continue;
+                }
+                Some('T') => {
+                    tokens.extend(Self::new("%H:%M:%S")?.0);
+                    continue;
+                }
+                Some('.') if chars.next() == Some('f') => Spec::DotNanos,
+                Some(':') if chars.next() == Some('z') => Spec::ColonOffset,
+                _ => return Err(format!("unsupported format: {format}").into()),
+            };
+            tokens.push(Token::Spec(spec));
+        }
+        Ok(Format(tokens))
+    }
+
+    // Parse all of `value`, surrounding whitespace aside
+    pub(crate) fn parse(&self, value: &str) -> Option<DateTime> {
+        let mut input = value.trim();
+        let mut dt = DateTime {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+            offset: None,
+        };
+
+        for token in &self.0 {
+            match token {
+                Token::Literal(c) => input = input.strip_prefix(*c)?,
+                Token::Spec(Spec::Year) => {
+                    let negative = input.starts_with('-');
+                    if negative || input.starts_with('+') {
+                        input = &input[1..];
+                    }
+                    let year = i64::from(take_digits(&mut input, 4)?);
+                    dt.year = if negative { -year } else { year };
+                }
+                Token::Spec(Spec::ShortYear) => {
+                    let year = i64::from(take_digits(&mut input, 2)?);
+                    dt.year = if year < 70 { 2000 + year } else { 1900 + year };
+                }
+                Token::Spec(Spec::Month) => dt.month = take_digits(&mut input, 2)?,
+                Token::Spec(Spec::MonthName) => {
+                    let index = MONTH_NAMES.iter().position(|name| {
+                        input
+                            .get(..3)
+                            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+                    })?;
+                    dt.month = index as u32 + 1;
+                    input = &input[3..];
+                }
+                Token::Spec(Spec::Day) => dt.day = take_digits(&mut input, 2)?,
+                Token::Spec(Spec::Hour) => dt.hour = take_digits(&mut input, 2)?,
+                Token::Spec(Spec::Minute) => dt.minute = take_digits(&mut input, 2)?,
+                Token::Spec(Spec::Second) => dt.second = take_digits(&mut input, 2)?,
+                Token::Spec(Spec::Nanos) => dt.nanosecond = take_nanos(&mut input)?,
+                Token::Spec(Spec::DotNanos) => {
+                    if let Some(rest) = input.strip_prefix('.') {
+                        input = rest;
+                        dt.nanosecond = take_nanos(&mut input)?;
+                    }
+                }
+                Token::Spec(Spec::Offset | Spec::ColonOffset) => {
+                    dt.offset = Some(take_offset(&mut input)?);
+                }
+            }
+        }
+
+        let valid = input.is_empty()
+            && (1..=12).contains(&dt.month)
+            && (1..=days_in_month(dt.year, dt.month)).contains(&dt.day)
+            && dt.hour < 24
+            && dt.minute < 60
+            && dt.second < 60;
+        valid.then_some(dt)
+    }
+
+    // The same format with any offset specifier dropped
+    fn without_offset(&self) -> Self {
+        let tokens = self
+            .0
+            .iter()
+            .filter(|token| !matches!(token, Token::Spec(Spec::Offset | Spec::ColonOffset)));
+        Format(tokens.cloned().collect())
+    }
+
+    // Offsets are omitted for naive timestamps rather than assumed to be UTC
+    pub(crate) fn format(&self, dt: &DateTime) -> String {
+        let mut out = String::new();
+        for token in &self.0 {
+            // Writing to a String cannot fail
+            let _ = match token {
+                Token::Literal(c) => write!(out, "{c}"),
+                Token::Spec(Spec::Year) => write!(out, "{:04}", dt.year),
+                Token::Spec(Spec::ShortYear) => write!(out, "{:02}", dt.year.rem_euclid(100)),
+                Token::Spec(Spec::Month) => write!(out, "{:02}", dt.month),
+                Token::Spec(Spec::MonthName) => {
+                    write!(out, "{}", MONTH_NAMES[dt.month as usize - 1])
+                }
+                Token::Spec(Spec::Day) => write!(out, "{:02}", dt.day),
+                Token::Spec(Spec::Hour) => write!(out, "{:02}", dt.hour),
+                Token::Spec(Spec::Minute) => write!(out, "{:02}", dt.minute),
+                Token::Spec(Spec::Second) => write!(out, "{:02}", dt.second),
+                Token::Spec(Spec::Nanos) => write!(out, "{:09}", dt.nanosecond),
+                // Shortest of millis, micros, or nanos; nothing for whole seconds
+                Token::Spec(Spec::DotNanos) => match dt.nanosecond {
+                    0 => Ok(()),
+                    n if n % 1_000_000 == 0 => write!(out, ".{:03}", n / 1_000_000),
+                    n if n % 1_000 == 0 => write!(out, ".{:06}", n / 1_000),
+                    n => write!(out, ".{n:09}"),
+                },
+                Token::Spec(spec @ (Spec::Offset | Spec::ColonOffset)) => match dt.offset {
+                    None => Ok(()),
+                    Some(offset) => {
+                        let sign = if offset < 0 { '-' } else { '+' };
+                        let (hours, minutes) = (offset.abs() / 3600, offset.abs() % 3600 / 60);
+                        let colon = if *spec == Spec::ColonOffset { ":" } else { "" };
+                        write!(out, "{sign}{hours:02}{colon}{minutes:02}")
+                    }
+                },
+            };
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemporalOrder {
+    Ordered,
+    // (row_index, value) for every row that breaks the order with the
+    // timestamp before it
+    OutOfOrder(Vec<(usize, String)>),
+}
+
+// Check that timestamps in `column` never go backwards (or forwards, for
+// descending order). Equal timestamps are in order and empty fields are
+// skipped; any other value that doesn't match `format` is an error.
+pub fn validate_temporal_order(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    format: &str,
+    order: SortOrder,
+) -> Result<TemporalOrder, Box<dyn Error>> {
+    let format = Format::new(format)?;
+    let mut previous: Option<i128> = None;
+    let mut out_of_order = Vec::new();
+
+    for (row, value) in column_values(ctx, delimiter, column)? {
+        if is_null_value(&value, &[]) {
+            continue;
+        }
+        let Some(parsed) = format.parse(&value) else {
+            return Err(format!("row {row}: cannot parse timestamp {value:?}").into());
+        };
+        let current = parsed.epoch_nanos();
+        if let Some(previous) = previous {
+            let ordered = match order {
+                SortOrder::Ascending => current >= previous,
+                SortOrder::Descending => current <= previous,
+            };
+            if !ordered {
+                out_of_order.push((row, value));
+            }
+        }
+        previous = Some(current);
+    }
+
+    if out_of_order.is_empty() {
+        Ok(TemporalOrder::Ordered)
+    } else {
+        Ok(TemporalOrder::OutOfOrder(out_of_order))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimestampNormalization {
+    pub normalized: usize,
+    pub parse_failed: usize,
+}
+
+// Rewrite every timestamp in `column` with `output_format`, parsing with the
+// first of `input_formats` that matches. Empty fields are skipped and values
+// no format matches are left unchanged.
+pub fn normalize_timestamps(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    input_formats: &[&str],
+    output_format: &str,
+) -> Result<TimestampNormalization, Box<dyn Error>> {
+    let inputs = input_formats
+        .iter()
+        .map(|format| Format::new(format))
+        .collect::<Result<Vec<_>, _>>()?;
+    let output = Format::new(output_format)?;
+
+    let mut parse_failed = 0;
+    let normalized = map_column(ctx, delimiter, column, |value| {
+        if is_null_value(value, &[]) {
+            return None;
+        }
+        match inputs.iter().find_map(|format| format.parse(value)) {
+            Some(dt) => Some(output.format(&dt)),
+            None => {
+                parse_failed += 1;
+                None
+            }
+        }
+    })?;
+    Ok(TimestampNormalization {
+        normalized,
+        parse_failed,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+}
+
+impl EpochUnit {
+    fn nanos(self) -> i128 {
+        match self {
+            EpochUnit::Seconds => 1_000_000_000,
+            EpochUnit::Milliseconds => 1_000_000,
+            EpochUnit::Microseconds => 1_000,
+        }
+    }
+}
+
+// `UTC`, `Z`, or a fixed offset such as `+05:30`. Named zones would need the
+// tz database, so they are rejected.
+fn parse_timezone(timezone: &str) -> Result<i32, Box<dyn Error>> {
+    if timezone.eq_ignore_ascii_case("utc") {
+        return Ok(0);
+    }
+    let mut input = timezone;
+    match take_offset(&mut input) {
+        Some(offset) if input.is_empty() => Ok(offset),
+        _ => Err(format!("unsupported timezone: {timezone}").into()),
+    }
+}
+
+// Replace Unix timestamps in `column` with ISO 8601 strings at `timezone`.
+// Fields that aren't integers are left unchanged. Returns the fields converted.
+pub fn epoch_to_iso8601(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    unit: EpochUnit,
+    timezone: &str,
+) -> Result<usize, Box<dyn Error>> {
+    let offset = parse_timezone(timezone)?;
+    let output = Format::new("%Y-%m-%dT%H:%M:%S%.f%:z")?;
+    map_column(ctx, delimiter, column, |value| {
+        let epoch = value.trim().parse::<i64>().ok()?;
+        let dt = DateTime::from_epoch_nanos(i128::from(epoch) * unit.nanos(), offset);
+        Some(output.format(&dt))
+    })
+}
+
+// Replace timestamps in `column` matching `input_format` with Unix timestamps
+// in `unit`, rounded down. Naive timestamps are taken as UTC and unparseable
+// fields are left unchanged. Returns the fields converted.
+pub fn iso8601_to_epoch(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    input_format: &str,
+    unit: EpochUnit,
+) -> Result<usize, Box<dyn Error>> {
+    let input = Format::new(input_format)?;
+    map_column(ctx, delimiter, column, |value| {
+        let dt = input.parse(value)?;
+        Some(dt.epoch_nanos().div_euclid(unit.nanos()).to_string())
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimezoneAudit {
+    pub tz_aware: usize,
+    pub tz_naive: usize,
+    pub unparseable: usize,
+}
+
+// Count timestamps in `column` that carry a UTC offset. Values matching
+// `format` with its offset specifier removed are naive; empty fields are
+// skipped.
+pub fn validate_tz_aware(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    format: &str,
+) -> Result<TimezoneAudit, Box<dyn Error>> {
+    let aware = Format::new(format)?;
+    let naive = aware.without_offset();
+    let mut audit = TimezoneAudit::default();
+    for (_, value) in column_values(ctx, delimiter, column)? {
+        if is_null_value(&value, &[]) {
+            continue;
+        }
+        match aware.parse(&value).or_else(|| naive.parse(&value)) {
+            Some(dt) if dt.offset.is_some() => audit.tz_aware += 1,
+            Some(_) => audit.tz_naive += 1,
+            None => audit.unparseable += 1,
+        }
+    }
+    Ok(audit)
+}
+
+// Append a column named `column_name` holding the current UTC time in ISO 8601.
+// The time is read once, so every row gets the same value. Short rows are
+// padded so the timestamp always lands under the new header.
+pub fn add_ingestion_timestamp(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column_name: &str,
+) -> Result<(), Box<dyn Error>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let timestamp =
+        Format::new("%Y-%m-%dT%H:%M:%S%.f%:z")?.format(&DateTime::from_epoch_nanos(now as i128, 0));
+
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first_mut() else {
+        return Ok(());
+    };
+    let width = header.len();
+    header.push(column_name.to_string());
+    for row in data.iter_mut() {
+        if row.len() < width {
+            row.resize(width, String::new());
+        }
+        row.insert(width, timestamp.clone());
+    }
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn formats_parse_and_reject() {
+        let iso = Format::new("%Y-%m-%dT%H:%M:%S%.f%:z").unwrap();
+        let dt = iso.parse("2024-02-29T12:30:00.5+01:00").unwrap();
+        assert_eq!(dt.epoch_nanos(), 1_709_206_200_500_000_000);
+        assert!(iso.parse("2023-02-29T12:30:00+01:00").is_none());
+
+        let day_first = Format::new("%d %b %Y").unwrap();
+        assert_eq!(day_first.parse("01 jan 1970").unwrap().epoch_nanos(), 0);
+        assert!(Format::new("%Q").is_err());
+    }
+
+    #[test]
+    fn temporal_order_is_validated() {
+        let c = ctx("ts\n2024-01-01 10:00\n2024-01-01 09:00\n\n2024-01-02 00:00\n");
+        let column = Column::from("ts");
+        let result =
+            validate_temporal_order(&c, b',', &column, "%Y-%m-%d %H:%M", SortOrder::Ascending);
+        assert_eq!(
+            result.unwrap(),
+            TemporalOrder::OutOfOrder(vec![(2, "2024-01-01 09:00".to_string())])
+        );
+        let desc = ctx("ts\n2024-01-02 00:00\n2024-01-01 09:00\n");
+        let result = validate_temporal_order(
+            &desc,
+            b',',
+            &column,
+            "%Y-%m-%d %H:%M",
+            SortOrder::Descending,
+        );
+        assert_eq!(result.unwrap(), TemporalOrder::Ordered);
+    }
+
+    #[test]
+    fn timestamps_are_normalized() {
+        let mut c = ctx("id,ts\n1,03/15/2024 08:05\n2,2024-03-16\n3,soon\n4,\n");
+        let report = normalize_timestamps(
+            &mut c,
+            b',',
+            &Column::from("ts"),
+            &["%m/%d/%Y %H:%M", "%F"],
+            "%Y-%m-%dT%H:%M:%S%.f%:z",
+        )
+        .unwrap();
+        assert_eq!(report.normalized, 2);
+        assert_eq!(report.parse_failed, 1);
+        assert_eq!(
+            c.processed_text,
+            "id,ts\n1,2024-03-15T08:05:00\n2,2024-03-16T00:00:00\n3,soon\n4,\n"
+        );
+    }
+
+    #[test]
+    fn epochs_are_converted() {
+        let mut c = ctx("ts\n0\n1709206200500\n-1\nn/a\n");
+        let converted =
+            epoch_to_iso8601(&mut c, b',', &0.into(), EpochUnit::Milliseconds, "+01:00").unwrap();
+        assert_eq!(converted, 3);
+        assert_eq!(
+            c.processed_text,
+            "ts\n1970-01-01T01:00:00+01:00\n2024-02-29T12:30:00.500+01:00\n\
+             1970-01-01T00:59:59.999+01:00\nn/a\n"
+        );
+        assert!(
+            epoch_to_iso8601(&mut c, b',', &0.into(), EpochUnit::Seconds, "Europe/Paris").is_err()
+        );
+    }
+
+    #[test]
+    fn timestamps_are_converted_to_epochs() {
+        let mut c = ctx("ts\n2024-02-29T12:30:00.5+01:00\n1969-12-31T23:59:59.9Z\nlater\n");
+        let format = "%Y-%m-%dT%H:%M:%S%.f%z";
+        let converted =
+            iso8601_to_epoch(&mut c, b',', &0.into(), format, EpochUnit::Seconds).unwrap();
+        assert_eq!(converted, 2);
+        assert_eq!(c.processed_text, "ts\n1709206200\n-1\nlater\n");
+    }
+
+    #[test]
+    fn timezone_awareness_is_audited() {
+        let c = ctx(
+            "ts\n2024-01-01T00:00:00Z\n2024-01-01T00:00:00\n2024-01-01T00:00:00-0500\nnope\n\n",
+        );
+        let audit = validate_tz_aware(&c, b',', &0.into(), "%Y-%m-%dT%H:%M:%S%z").unwrap();
+        assert_eq!(
+            audit,
+            TimezoneAudit {
+                tz_aware: 2,
+                tz_naive: 1,
+                unparseable: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn ingestion_timestamp_is_shared_by_all_rows() {
+        let mut c = ctx("a\n1\n2\n");
+        add_ingestion_timestamp(&mut c, b',', "ingested_at").unwrap();
+        let rows = read_rows(&c.processed_text, b',').unwrap();
+        assert_eq!(rows[0], ["a", "ingested_at"]);
+        assert_eq!(rows[1][1], rows[2][1]);
+        assert!(rows[1][1].ends_with("+00:00"));
+        let audit = validate_tz_aware(&c, b',', &1.into(), "%Y-%m-%dT%H:%M:%S%.f%:z").unwrap();
+        assert_eq!(audit.tz_aware, 2);
+    }
+
+    #[test]
+    fn ingestion_timestamp_pads_short_rows() {
+        let mut c = ctx("a,b\n1\n2,3\n");
+        add_ingestion_timestamp(&mut c, b',', "ingested_at").unwrap();
+        let rows = read_rows(&c.processed_text, b',').unwrap();
+        assert_eq!(rows[1][..2], ["1", ""]);
+        assert_eq!(rows[1][2], rows[2][2]);
+        assert!(rows[1][2].ends_with("+00:00"));
+    }
+}
@@ -0,0 +1,523 @@
+use std::error::Error;
+
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, scan_fields, split_records, trim_terminator, write_rows};
+use crate::validate::is_null_value;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    // Columns present in the file but not in the baseline
+    pub added: Vec<String>,
+    // Columns in the baseline that the file no longer has
+    pub removed: Vec<String>,
+    // Shared columns appear in a different relative order
+    pub reordered: bool,
+}
+
+// Compare the header row against `baseline`. With `reorder` set, columns are
+// rearranged to follow the baseline order, with added columns kept at the end.
+pub fn detect_schema_drift(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    baseline: &[String],
+    reorder: bool,
+) -> Result<SchemaDrift, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(SchemaDrift {
+            removed: baseline.to_vec(),
+            ..SchemaDrift::default()
+        });
+    };
+
+    let added: Vec<String> = header
+        .iter()
+        .filter(|h| !baseline.contains(h))
+        .cloned()
+        .collect();
+    let removed: Vec<String> = baseline
+        .iter()
+        .filter(|b| !header.contains(b))
+        .cloned()
+        .collect();
+    let current_shared = header.iter().filter(|h| baseline.contains(h));
+    let baseline_shared = baseline.iter().filter(|b| header.contains(b));
+    let reordered = !current_shared.eq(baseline_shared);
+
+    if reorder && reordered {
+        let order: Vec<usize> = baseline
+            .iter()
+            .filter_map(|b| header.iter().position(|h| h == b))
+            .chain(
+                header
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, h)| added.contains(h))
+                    .map(|(i, _)| i),
+            )
+            .collect();
+        for row in rows.iter_mut() {
+            *row = order
+                .iter()
+                .map(|&i| row.get(i).cloned().unwrap_or_default())
+                .collect();
+        }
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+
+    Ok(SchemaDrift {
+        added,
+        removed,
+        reordered,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotingInconsistency {
+    pub column: usize,
+    pub quoted_count: usize,
+    pub unquoted_count: usize,
+}
+
+// Find columns whose data values are sometimes quoted and sometimes not.
+// Values that must be quoted (delimiter, quote, or line break) are ignored.
+pub fn detect_inconsistent_quoting(ctx: &CsvContext, delimiter: u8) -> Vec<QuotingInconsistency> {
+    let mut counts: Vec<(usize, usize)> = Vec::new();
+    for row in scan_fields(&ctx.processed_text, delimiter).iter().skip(1) {
+        if counts.len() < row.len() {
+            counts.resize(row.len(), (0, 0));
+        }
+        for (column, field) in row.iter().enumerate() {
+            if field.needs_quotes(delimiter) {
+                continue;
+            }
+            if field.quoted {
+                counts[column].0 += 1;
+            } else {
+                counts[column].1 += 1;
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (quoted, unquoted))| *quoted > 0 && *unquoted > 0)
+        .map(
+            |(column, (quoted_count, unquoted_count))| QuotingInconsistency {
+                column,
+                quoted_count,
+                unquoted_count,
+            },
+        )
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeaderRowEstimate {
+    pub likely_header_rows: usize,
+    pub confidence: f64,
+}
+
+// Rows sampled from the top of the file when looking for header rows
+const HEADER_SAMPLE_ROWS: usize = 20;
+
+fn looks_like_data_row(row: &[String]) -> bool {
+    row.iter().any(|field| field.trim().parse::<f64>().is_ok())
+}
+
+// Estimate how many leading rows are headers. Header rows hold only text
+// (merged cells show up as empty fields); the first row with a numeric field
+// is taken as the first data row. Confidence is the share of the sampled rows
+// after the headers that also look like data.
+pub fn detect_header_row_count(
+    ctx: &CsvContext,
+    delimiter: u8,
+) -> Result<HeaderRowEstimate, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let sample = &rows[..rows.len().min(HEADER_SAMPLE_ROWS)];
+    if sample.is_empty() {
+        return Ok(HeaderRowEstimate {
+            likely_header_rows: 0,
+            confidence: 0.0,
+        });
+    }
+
+    let Some(first_data) = sample.iter().position(|row| looks_like_data_row(row)) else {
+        // All text: assume a single header and admit we cannot tell
+        return Ok(HeaderRowEstimate {
+            likely_header_rows: 1,
+            confidence: 0.5,
+        });
+    };
+    let likely_header_rows = first_data.max(1);
+    let body = &sample[likely_header_rows..];
+    let confidence = if body.is_empty() {
+        0.5
+    } else {
+        body.iter().filter(|row| looks_like_data_row(row)).count() as f64 / body.len() as f64
+    };
+
+    Ok(HeaderRowEstimate {
+        likely_header_rows,
+        confidence,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordLength {
+    LikelyFixedWidth { record_length: usize },
+    VariableWidth { min: usize, max: usize, mean: f64 },
+}
+
+// Check whether every logical row has the same byte length, measured without
+// the line terminator. Blank lines are ignored.
+pub fn detect_fixed_record_length(ctx: &CsvContext) -> RecordLength {
+    let lengths: Vec<usize> = split_records(&ctx.processed_text)
+        .into_iter()
+        .map(|record| trim_terminator(record).len())
+        .filter(|&length| length > 0)
+        .collect();
+
+    let min = lengths.iter().copied().min().unwrap_or(0);
+    let max = lengths.iter().copied().max().unwrap_or(0);
+    if !lengths.is_empty() && min == max {
+        return RecordLength::LikelyFixedWidth { record_length: min };
+    }
+    let mean = if lengths.is_empty() {
+        0.0
+    } else {
+        lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+    };
+    RecordLength::VariableWidth { min, max, mean }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZeroByteRows {
+    // Row indices where row 0 is the header
+    pub zero_byte_rows: Vec<usize>,
+    pub total: usize,
+}
+
+// A row whose fields hold nothing but `\0` (empty fields allowed), as written
+// by exporters that dump database nulls as zero bytes
+pub(crate) fn is_zero_byte_row(row: &[String]) -> bool {
+    row.iter().any(|field| field.contains('\0'))
+        && row.iter().all(|field| field.chars().all(|c| c == '\0'))
+}
+
+pub fn detect_zero_byte_rows(
+    ctx: &CsvContext,
+    delimiter: u8,
+) -> Result<ZeroByteRows, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let zero_byte_rows: Vec<usize> = rows
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, row)| is_zero_byte_row(row))
+        .map(|(index, _)| index)
+        .collect();
+    Ok(ZeroByteRows {
+        total: zero_byte_rows.len(),
+        zero_byte_rows,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NullBytes {
+    pub null_byte_count: usize,
+    // Byte offset into `raw_data`
+    pub first_offset: Option<usize>,
+}
+
+// Count `0x00` bytes in `raw_data` without modifying it; see
+// `repair::remove_null_bytes`
+pub fn detect_null_bytes(ctx: &CsvContext) -> NullBytes {
+    NullBytes {
+        null_byte_count: ctx.raw_data.iter().filter(|&&byte| byte == 0).count(),
+        first_offset: ctx.raw_data.iter().position(|&byte| byte == 0),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataStart {
+    pub start_row_index: usize,
+    pub skipped_rows: usize,
+}
+
+// At least two fields, mostly filled in, and all text. Values starting with a
+// digit (numbers, dates, timestamps) are not considered text.
+fn looks_like_header_row(row: &[String]) -> bool {
+    let filled: Vec<&str> = row
+        .iter()
+        .map(|f| f.trim())
+        .filter(|f| !f.is_empty())
+        .collect();
+    row.len() >= 2
+        && filled.len() * 2 > row.len()
+        && filled
+            .iter()
+            .all(|f| f.parse::<f64>().is_err() && !f.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+// Find the first line that looks like a CSV header, skipping title rows,
+// timestamps, and blank lines that some exports place above the data. Indices
+// count physical records, blank lines included.
+pub fn detect_data_start_row(ctx: &CsvContext, delimiter: u8) -> Result<DataStart, Box<dyn Error>> {
+    for (index, record) in split_records(&ctx.processed_text).into_iter().enumerate() {
+        let row = read_rows(record, delimiter)?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        if looks_like_header_row(&row) {
+            return Ok(DataStart {
+                start_row_index: index,
+                skipped_rows: index,
+            });
+        }
+    }
+    Err("no row looks like a CSV header".into())
+}
+
+fn parse_record(record: &str, delimiter: u8) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(read_rows(record, delimiter)?
+        .into_iter()
+        .next()
+        .unwrap_or_default())
+}
+
+// All fields numeric or empty except exactly one label
+fn is_summary_shaped(row: &[String]) -> bool {
+    let text_fields = row
+        .iter()
+        .filter(|f| !f.trim().is_empty() && f.trim().parse::<f64>().is_err())
+        .count();
+    text_fields == 1 && row.len() > 1
+}
+
+// Look at the last `n_sample` physical records for trailing summary rows:
+// blank lines, rows with the wrong field count, rows mentioning a total, or
+// numeric rows with a single label when data rows are not shaped that way.
+// Returns the record index where the trailer starts, if any.
+pub fn detect_trailer_rows(
+    ctx: &CsvContext,
+    delimiter: u8,
+    n_sample: usize,
+) -> Result<Option<usize>, Box<dyn Error>> {
+    let records = split_records(&ctx.processed_text);
+    let Some(header_index) = records.iter().position(|r| !trim_terminator(r).is_empty()) else {
+        return Ok(None);
+    };
+    let expected_fields = parse_record(records[header_index], delimiter)?.len();
+    let data_is_summary_shaped = match records.get(header_index + 1) {
+        Some(record) => is_summary_shaped(&parse_record(record, delimiter)?),
+        None => false,
+    };
+
+    let window_start = records.len().saturating_sub(n_sample).max(header_index + 1);
+    let mut trailer_start = None;
+    for index in (window_start..records.len()).rev() {
+        let record = trim_terminator(records[index]);
+        let row = parse_record(record, delimiter)?;
+        let is_trailer = record.trim().is_empty()
+            || row.len() != expected_fields
+            || row.iter().any(|f| f.to_lowercase().contains("total"))
+            || (!data_is_summary_shaped && is_summary_shaped(&row));
+        if !is_trailer {
+            break;
+        }
+        trailer_start = Some(index);
+    }
+    Ok(trailer_start)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseColumn {
+    pub column: String,
+    pub null_ratio: f64,
+    pub null_count: usize,
+    pub total: usize,
+}
+
+// Report columns whose share of empty values exceeds `threshold`. Trailing
+// delimiters and misaligned schemas tend to produce these.
+pub fn detect_sparse_columns(
+    ctx: &CsvContext,
+    delimiter: u8,
+    threshold: f64,
+) -> Result<Vec<SparseColumn>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut sparse = Vec::new();
+    for (index, name) in header.iter().enumerate() {
+        let null_count = data
+            .iter()
+            .filter(|row| is_null_value(row.get(index).map_or("", |v| v), &[]))
+            .count();
+        let null_ratio = null_count as f64 / data.len() as f64;
+        if null_ratio > threshold {
+            sparse.push(SparseColumn {
+                column: name.clone(),
+                null_ratio,
+                null_count,
+                total: data.len(),
+            });
+        }
+    }
+    Ok(sparse)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultilineFields {
+    // (logical row index, field index) of every field holding a `\n`
+    pub locations: Vec<(usize, usize)>,
+    pub total: usize,
+}
+
+// Find quoted fields that span lines, which some parsers cannot handle
+pub fn detect_multiline_fields(ctx: &CsvContext, delimiter: u8) -> MultilineFields {
+    let locations: Vec<(usize, usize)> = scan_fields(&ctx.processed_text, delimiter)
+        .iter()
+        .enumerate()
+        .flat_map(|(row_index, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, field)| field.value.contains('\n'))
+                .map(move |(field_index, _)| (row_index, field_index))
+        })
+        .collect();
+    MultilineFields {
+        total: locations.len(),
+        locations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn schema_drift_reports_and_reorders() {
+        let mut c = ctx("b,a,extra\n2,1,x\n");
+        let drift = detect_schema_drift(&mut c, b',', &names(&["a", "b", "gone"]), true).unwrap();
+        assert_eq!(drift.added, names(&["extra"]));
+        assert_eq!(drift.removed, names(&["gone"]));
+        assert!(drift.reordered);
+        assert_eq!(c.processed_text, "a,b,extra\n1,2,x\n");
+    }
+
+    #[test]
+    fn inconsistent_quoting_is_reported_per_column() {
+        let c = ctx("name,city\n\"Alice\",Oslo\nBob,\"Bergen, NO\"\n\"Carol\",Rome\n");
+        let found = detect_inconsistent_quoting(&c, b',');
+        assert_eq!(
+            found,
+            vec![QuotingInconsistency {
+                column: 0,
+                quoted_count: 2,
+                unquoted_count: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn multi_row_headers_are_detected() {
+        let c = ctx("Sales,,Costs\nQ1,Q2,Q1\n10,20,5\n11,21,6\n");
+        let estimate = detect_header_row_count(&c, b',').unwrap();
+        assert_eq!(estimate.likely_header_rows, 2);
+        assert_eq!(estimate.confidence, 1.0);
+    }
+
+    #[test]
+    fn fixed_record_length_is_detected() {
+        let fixed = ctx("ab,cd\r\n12,34\r\n");
+        assert_eq!(
+            detect_fixed_record_length(&fixed),
+            RecordLength::LikelyFixedWidth { record_length: 5 }
+        );
+        let variable = ctx("a,b\n1,234\n");
+        assert_eq!(
+            detect_fixed_record_length(&variable),
+            RecordLength::VariableWidth {
+                min: 3,
+                max: 5,
+                mean: 4.0,
+            }
+        );
+    }
+
+    #[test]
+    fn zero_byte_rows_are_found() {
+        let c = ctx("a,b\n1,2\n\0,\0\0\n\0,x\n");
+        let found = detect_zero_byte_rows(&c, b',').unwrap();
+        assert_eq!(found.zero_byte_rows, vec![2]);
+        assert_eq!(found.total, 1);
+    }
+
+    #[test]
+    fn data_start_skips_title_rows() {
+        let c = ctx("Monthly Report\nGenerated,2024-01-01,\n\nname,amount\nAda,10\n");
+        assert_eq!(
+            detect_data_start_row(&c, b',').unwrap(),
+            DataStart {
+                start_row_index: 3,
+                skipped_rows: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn trailer_rows_are_detected() {
+        let c = ctx("item,q1,q2\nnuts,1,2\nbolts,3,4\n\nGrand Total,4,6\n");
+        assert_eq!(detect_trailer_rows(&c, b',', 5).unwrap(), Some(3));
+        let clean = ctx("item,q1,q2\nnuts,1,2\n");
+        assert_eq!(detect_trailer_rows(&clean, b',', 5).unwrap(), None);
+    }
+
+    #[test]
+    fn sparse_columns_exceed_threshold() {
+        let c = ctx("a,b,\n1,,\n2,x,\n3,,\n4,,\n");
+        let sparse = detect_sparse_columns(&c, b',', 0.8).unwrap();
+        assert_eq!(
+            sparse,
+            vec![SparseColumn {
+                column: String::new(),
+                null_ratio: 1.0,
+                null_count: 4,
+                total: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn multiline_fields_are_located() {
+        let c = ctx("a,b\n\"x\ny\",1\n2,\"p\r\nq\"\n");
+        let found = detect_multiline_fields(&c, b',');
+        assert_eq!(found.locations, vec![(1, 0), (2, 1)]);
+        assert_eq!(found.total, 2);
+    }
+
+    #[test]
+    fn null_bytes_are_counted() {
+        let c = ctx("a,b\n1\x00,\x002\n");
+        let found = detect_null_bytes(&c);
+        assert_eq!(found.null_byte_count, 2);
+        assert_eq!(found.first_offset, Some(5));
+        assert_eq!(detect_null_bytes(&ctx("a\n")), NullBytes::default());
+    }
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+
+use crate::builder::CsvRepairBuilder;
+use crate::context::CsvContext;
+use crate::repair::RepairReport;
+
+// Bytes of body data inspected after the header row
+const BODY_SAMPLE_BYTES: usize = 64 * 1024;
+
+// Coarse classification of a byte slice. This stands in for a full charset
+// detector: pure ASCII fits any encoding, valid UTF-8 with non-ASCII bytes is
+// UTF-8, and other text is assumed to be a Windows-1252 export. NUL bytes
+// (UTF-16/32) or bytes Windows-1252 leaves undefined make it "unknown".
+fn classify(bytes: &[u8]) -> &'static str {
+    if bytes.is_ascii() && !bytes.contains(&0) {
+        "ascii"
+    } else if std::str::from_utf8(bytes).is_ok() && !bytes.contains(&0) {
+        "utf-8"
+    } else if bytes
+        .iter()
+        .any(|b| matches!(b, 0x00 | 0x81 | 0x8D | 0x8F | 0x90 | 0x9D))
+    {
+        "unknown"
+    } else {
+        "windows-1252"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingCheck {
+    Consistent {
+        encoding: String,
+    },
+    MismatchDetected {
+        header_encoding: String,
+        body_encoding: String,
+    },
+}
+
+// Compare the encoding of the header row against a sample of the data rows
+pub fn detect_header_body_encoding_mismatch(ctx: &CsvContext) -> EncodingCheck {
+    let data = &ctx.raw_data;
+    let header_end = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(data.len(), |i| i + 1);
+    let body = &data[header_end..];
+    let mut sample = &body[..body.len().min(BODY_SAMPLE_BYTES)];
+    // Don't let the cut split a multi-byte UTF-8 sequence: end at the last
+    // newline, or back up over continuation bytes when the line is longer
+    if sample.len() < body.len() {
+        if let Some(end) = sample.iter().rposition(|&b| b == b'\n') {
+            sample = &sample[..=end];
+        } else {
+            let mut end = sample.len();
+            while end > sample.len().saturating_sub(3) && body[end] & 0xC0 == 0x80 {
+                end -= 1;
+            }
+            sample = &sample[..end];
+        }
+    }
+
+    let header_encoding = classify(&data[..header_end]);
+    let body_encoding = classify(sample);
+    match (header_encoding, body_encoding) {
+        (header, body) if header == body => EncodingCheck::Consistent {
+            encoding: header.to_string(),
+        },
+        ("ascii", other) | (other, "ascii") => EncodingCheck::Consistent {
+            encoding: other.to_string(),
+        },
+        (header, body) => EncodingCheck::MismatchDetected {
+            header_encoding: header.to_string(),
+            body_encoding: body.to_string(),
+        },
+    }
+}
+
+// Windows-1252 characters for bytes 0x80..=0x9F; the five undefined bytes
+// map to the matching C1 control like browsers do
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+// A truncated trailing byte decodes to U+FFFD rather than being dropped
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> String {
+    let pairs = bytes.chunks_exact(2);
+    let truncated = !pairs.remainder().is_empty();
+    let units = pairs.map(|pair| {
+        if little_endian {
+            u16::from_le_bytes([pair[0], pair[1]])
+        } else {
+            u16::from_be_bytes([pair[0], pair[1]])
+        }
+    });
+    let mut text: String = char::decode_utf16(units)
+        .map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if truncated {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
+
+// Decode bytes with a WHATWG-style encoding label. Only the encodings seen in
+// this project's exports are supported.
+pub fn decode_with_label(bytes: &[u8], encoding_label: &str) -> Result<String, Box<dyn Error>> {
+    let label = encoding_label.trim().to_ascii_lowercase();
+    let text = match label.as_str() {
+        "utf-8" | "utf8" | "unicode-1-1-utf-8" => String::from_utf8_lossy(bytes).into_owned(),
+        "us-ascii" | "ascii" | "windows-1252" | "cp1252" | "x-cp1252" => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+                _ => char::from(b),
+            })
+            .collect(),
+        "iso-8859-1" | "iso8859-1" | "latin1" | "l1" => {
+            bytes.iter().map(|&b| char::from(b)).collect()
+        }
+        "utf-16le" | "utf-16" => decode_utf16(bytes, true),
+        "utf-16be" => decode_utf16(bytes, false),
+        _ => return Err(format!("unsupported encoding label: {encoding_label}").into()),
+    };
+    Ok(text)
+}
+
+// Decode `raw_data` with a caller-supplied encoding instead of detecting it,
+// then strip any BOM and normalize line endings
+pub fn repair_with_encoding(
+    ctx: &mut CsvContext,
+    encoding_label: &str,
+) -> Result<RepairReport, Box<dyn Error>> {
+    ctx.processed_text = decode_with_label(&ctx.raw_data, encoding_label)?;
+    let (repaired, mut report) = CsvRepairBuilder::from_context(std::mem::take(ctx))
+        .strip_bom()
+        .normalize_line_endings()
+        .build_context();
+    *ctx = repaired;
+    report.events.insert(
+        0,
+        format!("decoded as {}", encoding_label.trim().to_ascii_lowercase()),
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8_header_with_windows_1252_body_is_a_mismatch() {
+        let mut data = "café,prix\n".as_bytes().to_vec();
+        data.extend_from_slice(b"cr\xE8me,3\n");
+        assert_eq!(
+            detect_header_body_encoding_mismatch(&CsvContext::new(data)),
+            EncodingCheck::MismatchDetected {
+                header_encoding: "utf-8".to_string(),
+                body_encoding: "windows-1252".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn ascii_header_is_compatible_with_any_body() {
+        let data = "name\nJosé\n".as_bytes().to_vec();
+        assert_eq!(
+            detect_header_body_encoding_mismatch(&CsvContext::new(data)),
+            EncodingCheck::Consistent {
+                encoding: "utf-8".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn utf16_is_unknown_and_long_lines_cut_on_char_boundary() {
+        let mut data = b"a\n".to_vec();
+        data.extend_from_slice(&[b'x', 0, b'\n', 0]);
+        assert_eq!(
+            detect_header_body_encoding_mismatch(&CsvContext::new(data)),
+            EncodingCheck::Consistent {
+                encoding: "unknown".to_string(),
+            }
+        );
+
+        let mut data = "é\n".as_bytes().to_vec();
+        data.extend(std::iter::repeat_n(b'x', BODY_SAMPLE_BYTES - 1));
+        data.extend_from_slice("é\n".as_bytes());
+        assert_eq!(
+            detect_header_body_encoding_mismatch(&CsvContext::new(data)),
+            EncodingCheck::Consistent {
+                encoding: "utf-8".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn forced_windows_1252_decode() {
+        let mut ctx = CsvContext::new(b"name\r\ncr\xE8me \x80\r\n".to_vec());
+        let report = repair_with_encoding(&mut ctx, "Windows-1252").unwrap();
+        assert_eq!(ctx.processed_text, "name\ncrème €\n");
+        assert_eq!(report.events[0], "decoded as windows-1252");
+    }
+
+    #[test]
+    fn forced_utf16_decode_strips_bom() {
+        let mut ctx = CsvContext::new(vec![0xFF, 0xFE, b'a', 0, b'\n', 0]);
+        repair_with_encoding(&mut ctx, "utf-16le").unwrap();
+        assert_eq!(ctx.processed_text, "a\n");
+        assert!(repair_with_encoding(&mut ctx, "shift_jis").is_err());
+    }
+
+    #[test]
+    fn odd_trailing_utf16_byte_is_replaced() {
+        assert_eq!(decode_utf16(&[b'a', 0, b'b'], true), "a\u{fffd}");
+        assert_eq!(decode_utf16(&[0, b'a', 0], false), "a\u{fffd}");
+    }
+}
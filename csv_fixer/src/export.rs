@@ -0,0 +1,601 @@
+use std::error::Error;
+use std::io::{self, BufWriter, Write};
+
+use crate::context::CsvContext;
+use crate::csv_io::read_rows;
+
+// Write `processed_text` to any writer, returning the number of bytes written
+pub fn write_to<W: Write>(ctx: &CsvContext, writer: W) -> io::Result<usize> {
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(ctx.processed_text.as_bytes())?;
+    writer.flush()?;
+    Ok(ctx.processed_text.len())
+}
+
+// Stream `processed_text` to stdout so the output can be piped to other tools
+pub fn write_to_stdout(ctx: &CsvContext) -> io::Result<usize> {
+    write_to(ctx, io::stdout().lock())
+}
+
+fn escape_pg_copy(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// Render data rows in PostgreSQL COPY text format for `COPY ... FROM STDIN`.
+// The header row is dropped since the text format has no header line, and
+// empty fields become `null_string`.
+pub fn to_pg_copy(
+    ctx: &CsvContext,
+    delimiter: u8,
+    null_string: &str,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut out = String::new();
+    for row in rows.iter().skip(1) {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|field| {
+                if field.is_empty() {
+                    null_string.to_string()
+                } else {
+                    escape_pg_copy(field)
+                }
+            })
+            .collect();
+        out.push_str(&fields.join("\t"));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+// Mirrors the FIELDS/LINES clauses of a MySQL `LOAD DATA INFILE` statement.
+// Defaults match MySQL's own defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MySqlLoadOptions {
+    pub fields_terminated_by: String,
+    pub enclosed_by: Option<char>,
+    pub escaped_by: Option<char>,
+    pub lines_terminated_by: String,
+}
+
+impl Default for MySqlLoadOptions {
+    fn default() -> Self {
+        Self {
+            fields_terminated_by: "\t".to_string(),
+            enclosed_by: None,
+            escaped_by: Some('\\'),
+            lines_terminated_by: "\n".to_string(),
+        }
+    }
+}
+
+// Without an escape character MySQL only recognizes a doubled enclosure
+// character inside an enclosed field. With neither option a value holding a
+// terminator cannot be written, so None is returned.
+fn escape_mysql(value: &str, options: &MySqlLoadOptions) -> Option<String> {
+    let Some(escape) = options.escaped_by else {
+        return match options.enclosed_by {
+            Some(quote) => Some(value.replace(quote, &format!("{quote}{quote}"))),
+            None => {
+                let holds = |terminator: &str| !terminator.is_empty() && value.contains(terminator);
+                let unsafe_value =
+                    holds(&options.fields_terminated_by) || holds(&options.lines_terminated_by);
+                (!unsafe_value).then(|| value.to_string())
+            }
+        };
+    };
+    let field_start = options.fields_terminated_by.chars().next();
+    let line_start = options.lines_terminated_by.chars().next();
+
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\0' {
+            out.push(escape);
+            out.push('0');
+            continue;
+        }
+        if c == escape
+            || Some(c) == options.enclosed_by
+            || (options.enclosed_by.is_none() && (Some(c) == field_start || Some(c) == line_start))
+        {
+            out.push(escape);
+        }
+        out.push(c);
+    }
+    Some(out)
+}
+
+// Render data rows for `LOAD DATA INFILE` using the given clauses. The
+// header row is dropped. Fails on a field holding a terminator when neither
+// an escape nor an enclosure character is set.
+pub fn to_mysql_load(
+    ctx: &CsvContext,
+    delimiter: u8,
+    options: &MySqlLoadOptions,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut out = String::new();
+    for (row_index, row) in rows.iter().enumerate().skip(1) {
+        let fields = row
+            .iter()
+            .enumerate()
+            .map(|(column, field)| {
+                let escaped = escape_mysql(field, options).ok_or_else(|| {
+                    format!(
+                        "row {row_index}, column {column}: field contains a terminator \
+                         and neither ESCAPED BY nor ENCLOSED BY is set"
+                    )
+                })?;
+                Ok(match options.enclosed_by {
+                    Some(quote) => format!("{quote}{escaped}{quote}"),
+                    None => escaped,
+                })
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+        out.push_str(&fields.join(&options.fields_terminated_by));
+        out.push_str(&options.lines_terminated_by);
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlTableOptions {
+    // Limit on data rows, for previews
+    pub max_rows: Option<usize>,
+    pub table_id: Option<String>,
+    pub css_class: Option<String>,
+}
+
+pub(crate) fn html_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// Render the CSV as an HTML table with the header row in <thead>
+pub fn to_html_table(
+    ctx: &CsvContext,
+    delimiter: u8,
+    options: &HtmlTableOptions,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut out = String::from("<table");
+    if let Some(id) = &options.table_id {
+        out.push_str(&format!(" id=\"{}\"", html_escape(id)));
+    }
+    if let Some(class) = &options.css_class {
+        out.push_str(&format!(" class=\"{}\"", html_escape(class)));
+    }
+    out.push_str(">\n");
+
+    let cells = |row: &[String], tag: &str| -> String {
+        let cells: String = row
+            .iter()
+            .map(|field| format!("<{tag}>{}</{tag}>", html_escape(field)))
+            .collect();
+        format!("    <tr>{cells}</tr>\n")
+    };
+
+    if let Some((header, data)) = rows.split_first() {
+        out.push_str("  <thead>\n");
+        out.push_str(&cells(header, "th"));
+        out.push_str("  </thead>\n  <tbody>\n");
+        let limit = options.max_rows.unwrap_or(data.len());
+        for row in data.iter().take(limit) {
+            out.push_str(&cells(row, "td"));
+        }
+        out.push_str("  </tbody>\n");
+    }
+    out.push_str("</table>\n");
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownTableOptions {
+    pub max_rows: Option<usize>,
+    pub align: Alignment,
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn pad(value: &str, width: usize, align: Alignment) -> String {
+    let fill = width.saturating_sub(value.chars().count());
+    match align {
+        Alignment::Left => format!("{value}{}", " ".repeat(fill)),
+        Alignment::Right => format!("{}{value}", " ".repeat(fill)),
+        Alignment::Center => {
+            let left = fill / 2;
+            format!("{}{value}{}", " ".repeat(left), " ".repeat(fill - left))
+        }
+    }
+}
+
+// Render the CSV as a padded Markdown pipe table
+pub fn to_markdown_table(
+    ctx: &CsvContext,
+    delimiter: u8,
+    options: &MarkdownTableOptions,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(String::new());
+    };
+    let limit = options.max_rows.unwrap_or(data.len());
+    let table: Vec<Vec<String>> = std::iter::once(header)
+        .chain(data.iter().take(limit))
+        .map(|row| {
+            (0..header.len())
+                .map(|i| markdown_cell(row.get(i).map_or("", |v| v)))
+                .collect()
+        })
+        .collect();
+
+    // The separator row needs at least three characters per column
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            table
+                .iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut out = String::new();
+    for (index, row) in table.iter().enumerate() {
+        out.push_str(&line(
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, &width)| pad(cell, width, options.align))
+                .collect(),
+        ));
+        if index == 0 {
+            out.push_str(&line(
+                widths
+                    .iter()
+                    .map(|&width| match options.align {
+                        Alignment::Left => format!(":{}", "-".repeat(width - 1)),
+                        Alignment::Center => format!(":{}:", "-".repeat(width - 2)),
+                        Alignment::Right => format!("{}:", "-".repeat(width - 1)),
+                    })
+                    .collect(),
+            ));
+        }
+    }
+    Ok(out)
+}
+
+// Numeric text that can be typed as a Number cell. A leading zero as in zip
+// codes (`02134`) would be dropped, so only `0` and `0.x` qualify, and Excel
+// keeps 15 significant digits, so longer values such as card numbers and IDs
+// stay strings.
+fn is_excel_number(value: &str) -> bool {
+    let digits = value.trim_start_matches(['-', '+']);
+    let leading_zero =
+        digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit());
+    let mantissa = digits.split(['e', 'E']).next().unwrap_or("");
+    let significant = mantissa
+        .trim_start_matches(['0', '.'])
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .count();
+    !leading_zero && significant <= 15 && value.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+// Render the CSV as a single-sheet SpreadsheetML 2003 workbook. Numeric
+// fields become Number cells; everything else is a String cell.
+pub fn to_excel_xml(
+    ctx: &CsvContext,
+    delimiter: u8,
+    sheet_name: &str,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut out = String::from(
+        "<?xml version=\"1.0\"?>\n\
+         <?mso-application progid=\"Excel.Sheet\"?>\n\
+         <Workbook xmlns=\"urn:schemas-microsoft-com:office:spreadsheet\"\n \
+         xmlns:ss=\"urn:schemas-microsoft-com:office:spreadsheet\">\n",
+    );
+    out.push_str(&format!(
+        " <Worksheet ss:Name=\"{}\">\n  <Table>\n",
+        xml_escape(sheet_name, true)
+    ));
+    for row in &rows {
+        out.push_str("   <Row>");
+        for field in row {
+            let (cell_type, value) = if is_excel_number(field.trim()) {
+                ("Number", field.trim())
+            } else {
+                ("String", field.as_str())
+            };
+            out.push_str(&format!(
+                "<Cell><Data ss:Type=\"{cell_type}\">{}</Data></Cell>",
+                xml_escape(value, false)
+            ));
+        }
+        out.push_str("</Row>\n");
+    }
+    out.push_str("  </Table>\n </Worksheet>\n</Workbook>\n");
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum XmlFields {
+    Attributes,
+    #[default]
+    Elements,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XmlOptions {
+    pub fields_as: XmlFields,
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+// Header text made into a valid element or attribute name
+fn xml_name(header: &str) -> String {
+    let mut name: String = header
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !is_xml_name(&name) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+// Escaped text with characters XML 1.0 forbids dropped. Attribute values
+// also encode whitespace so parsers don't normalize it away.
+fn xml_escape(value: &str, attribute: bool) -> String {
+    let allowed: String = value
+        .chars()
+        .filter(|&c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let escaped = html_escape(&allowed);
+    if !attribute {
+        return escaped;
+    }
+    escaped
+        .replace('\t', "&#9;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+}
+
+// Render each data row as a `row_element` under `root_element`, with fields
+// named after the header. Names are sanitized and made unique; fields past
+// the header are named `field_<n>`.
+pub fn to_xml(
+    ctx: &CsvContext,
+    delimiter: u8,
+    root_element: &str,
+    row_element: &str,
+    options: &XmlOptions,
+) -> Result<String, Box<dyn Error>> {
+    for name in [root_element, row_element] {
+        if !is_xml_name(name) {
+            return Err(format!("invalid XML element name: {name:?}").into());
+        }
+    }
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut out = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{root_element}>\n");
+    let Some((header, data)) = rows.split_first() else {
+        out.push_str(&format!("</{root_element}>\n"));
+        return Ok(out);
+    };
+
+    let mut names: Vec<String> = Vec::with_capacity(header.len());
+    for column in header {
+        let base = xml_name(column);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        names.push(name);
+    }
+    let name_at = |i: usize| names.get(i).cloned().unwrap_or(format!("field_{}", i + 1));
+
+    for row in data {
+        match options.fields_as {
+            XmlFields::Attributes => {
+                out.push_str(&format!("  <{row_element}"));
+                for (i, field) in row.iter().enumerate() {
+                    out.push_str(&format!(" {}=\"{}\"", name_at(i), xml_escape(field, true)));
+                }
+                out.push_str("/>\n");
+            }
+            XmlFields::Elements => {
+                out.push_str(&format!("  <{row_element}>\n"));
+                for (i, field) in row.iter().enumerate() {
+                    let name = name_at(i);
+                    out.push_str(&format!(
+                        "    <{name}>{}</{name}>\n",
+                        xml_escape(field, false)
+                    ));
+                }
+                out.push_str(&format!("  </{row_element}>\n"));
+            }
+        }
+    }
+    out.push_str(&format!("</{root_element}>\n"));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn write_to_returns_bytes_written() {
+        let mut out = Vec::new();
+        let written = write_to(&ctx("a,b\n1,2\n"), &mut out).unwrap();
+        assert_eq!(written, 8);
+        assert_eq!(out, b"a,b\n1,2\n");
+    }
+
+    #[test]
+    fn pg_copy_escapes_and_marks_nulls() {
+        let c = ctx("a,b,c\n\"x\ty\",,\"back\\slash\nline\"\n");
+        let out = to_pg_copy(&c, b',', "\\N").unwrap();
+        assert_eq!(out, "x\\ty\t\\N\tback\\\\slash\\nline\n");
+    }
+
+    #[test]
+    fn mysql_load_uses_default_clauses() {
+        let c = ctx("a,b\n\"x\ty\",\"back\\slash\"\n");
+        let out = to_mysql_load(&c, b',', &MySqlLoadOptions::default()).unwrap();
+        assert_eq!(out, "x\\\ty\tback\\\\slash\n");
+    }
+
+    #[test]
+    fn mysql_load_encloses_fields() {
+        let c = ctx("a,b\n\"say \"\"hi\"\"\",2\n");
+        let options = MySqlLoadOptions {
+            fields_terminated_by: ",".to_string(),
+            enclosed_by: Some('"'),
+            ..MySqlLoadOptions::default()
+        };
+        let out = to_mysql_load(&c, b',', &options).unwrap();
+        assert_eq!(out, "\"say \\\"hi\\\"\",\"2\"\n");
+
+        let options = MySqlLoadOptions {
+            escaped_by: None,
+            ..options
+        };
+        let out = to_mysql_load(&c, b',', &options).unwrap();
+        assert_eq!(out, "\"say \"\"hi\"\"\",\"2\"\n");
+
+        // Neither option set: terminators cannot be represented
+        let bare = MySqlLoadOptions {
+            enclosed_by: None,
+            escaped_by: None,
+            ..MySqlLoadOptions::default()
+        };
+        let c = ctx("a,b\nx,y\n");
+        assert_eq!(to_mysql_load(&c, b',', &bare).unwrap(), "x\ty\n");
+        let c = ctx("a,b\n\"x\ty\",2\n");
+        assert!(to_mysql_load(&c, b',', &bare).is_err());
+    }
+
+    #[test]
+    fn html_table_escapes_and_limits_rows() {
+        let c = ctx("name,note\nAda,\"<b>&\"\"\"\nAlan,x\n");
+        let options = HtmlTableOptions {
+            max_rows: Some(1),
+            table_id: Some("preview".to_string()),
+            css_class: None,
+        };
+        assert_eq!(
+            to_html_table(&c, b',', &options).unwrap(),
+            "<table id=\"preview\">\n  <thead>\n    <tr><th>name</th><th>note</th></tr>\n  </thead>\n  <tbody>\n    <tr><td>Ada</td><td>&lt;b&gt;&amp;&quot;</td></tr>\n  </tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn markdown_table_is_padded_and_escaped() {
+        let c = ctx("id,name\n1,Ada|L\n22,Al\n");
+        let options = MarkdownTableOptions {
+            max_rows: None,
+            align: Alignment::Right,
+        };
+        assert_eq!(
+            to_markdown_table(&c, b',', &options).unwrap(),
+            "|  id |   name |\n| --: | -----: |\n|   1 | Ada\\|L |\n|  22 |     Al |\n"
+        );
+    }
+
+    #[test]
+    fn excel_xml_types_cells() {
+        let c = ctx("name,qty\nA&B,3.5\n");
+        let xml = to_excel_xml(&c, b',', "Sheet <1>").unwrap();
+        assert!(xml.contains("<Worksheet ss:Name=\"Sheet &lt;1&gt;\">"));
+        assert!(xml.contains(
+            "<Row><Cell><Data ss:Type=\"String\">A&amp;B</Data></Cell><Cell><Data ss:Type=\"Number\">3.5</Data></Cell></Row>"
+        ));
+    }
+
+    #[test]
+    fn excel_xml_keeps_leading_zeros_and_drops_control_chars() {
+        let c =
+            ctx("zip,n\n02134,0\n-007,0.5\n\"a\x01b\",-0.25\n4111111111111111,123456789012345\n");
+        let xml = to_excel_xml(&c, b',', "s").unwrap();
+        assert!(xml.contains(
+            "<Row><Cell><Data ss:Type=\"String\">02134</Data></Cell><Cell><Data ss:Type=\"Number\">0</Data></Cell></Row>"
+        ));
+        assert!(xml.contains(
+            "<Row><Cell><Data ss:Type=\"String\">-007</Data></Cell><Cell><Data ss:Type=\"Number\">0.5</Data></Cell></Row>"
+        ));
+        assert!(xml.contains(
+            "<Row><Cell><Data ss:Type=\"String\">ab</Data></Cell><Cell><Data ss:Type=\"Number\">-0.25</Data></Cell></Row>"
+        ));
+        assert!(xml.contains(
+            "<Row><Cell><Data ss:Type=\"String\">4111111111111111</Data></Cell><Cell><Data ss:Type=\"Number\">123456789012345</Data></Cell></Row>"
+        ));
+    }
+
+    #[test]
+    fn xml_uses_elements_or_attributes() {
+        let c = ctx("id,first name,id\n1,A&B,x\n");
+        let elements = to_xml(&c, b',', "people", "person", &XmlOptions::default()).unwrap();
+        assert_eq!(
+            elements,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<people>\n  <person>\n    \
+             <id>1</id>\n    <first_name>A&amp;B</first_name>\n    <id_2>x</id_2>\n  \
+             </person>\n</people>\n"
+        );
+
+        let options = XmlOptions {
+            fields_as: XmlFields::Attributes,
+        };
+        let attributes = to_xml(&c, b',', "people", "person", &options).unwrap();
+        assert!(attributes.contains("<person id=\"1\" first_name=\"A&amp;B\" id_2=\"x\"/>"));
+        assert!(to_xml(&c, b',', "1people", "person", &options).is_err());
+    }
+}
@@ -0,0 +1,623 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::read_rows;
+use crate::transform::map_column;
+use crate::validate::column_values;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoneFormat {
+    E164,
+    National,
+    International,
+}
+
+// Regions numbered under the North American Numbering Plan (country code 1)
+const NANP_REGIONS: [&str; 2] = ["CA", "US"];
+
+// Format a phone number from its digits. Only NANP numbers carry enough
+// structure to be formatted without a metadata library; other numbers must be
+// written with a leading `+` and can only be emitted as E.164. Numbers
+// without a `+` are read as NANP only when the default region is in the plan.
+fn format_phone(value: &str, default_region: &str, format: PhoneFormat) -> Option<String> {
+    let value = value.trim();
+    let international = value.starts_with('+');
+    if value
+        .chars()
+        .any(|c| !(c.is_ascii_digit() || " ()-.+".contains(c)))
+    {
+        return None;
+    }
+    let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+    let nanp = international || NANP_REGIONS.contains(&default_region);
+
+    let national = match digits.len() {
+        10 if !international && nanp => &digits[..],
+        11 if nanp && digits.starts_with('1') => &digits[1..],
+        8..=15 if international => {
+            return (format == PhoneFormat::E164).then(|| format!("+{digits}"));
+        }
+        _ => return None,
+    };
+
+    // Area codes and exchanges never start with 0 or 1
+    let (area, exchange, line) = (&national[..3], &national[3..6], &national[6..]);
+    if area.starts_with(['0', '1']) || exchange.starts_with(['0', '1']) {
+        return None;
+    }
+    Some(match format {
+        PhoneFormat::E164 => format!("+1{national}"),
+        PhoneFormat::National => format!("({area}) {exchange}-{line}"),
+        PhoneFormat::International => format!("+1 {area}-{exchange}-{line}"),
+    })
+}
+
+// Reformat phone numbers in `column`. Values that cannot be parsed are left
+// unchanged. Returns (normalized, failed), where normalized counts only the
+// fields that changed; empty fields count as neither.
+pub fn normalize_phones(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    default_region: &str,
+    format: PhoneFormat,
+) -> Result<(usize, usize), Box<dyn Error>> {
+    let region = default_region.to_ascii_uppercase();
+    let mut failed = 0;
+    let normalized = map_column(ctx, delimiter, column, |value| {
+        if value.trim().is_empty() {
+            return None;
+        }
+        let Some(formatted) = format_phone(value, &region, format) else {
+            failed += 1;
+            return None;
+        };
+        (formatted != value).then_some(formatted)
+    })?;
+    Ok((normalized, failed))
+}
+
+// `^\d{5}(-\d{4})?$`
+pub(crate) fn is_us_zip(value: &str) -> bool {
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    match value.split_once('-') {
+        Some((zip, plus4)) => {
+            zip.len() == 5 && plus4.len() == 4 && all_digits(zip) && all_digits(plus4)
+        }
+        None => value.len() == 5 && all_digits(value),
+    }
+}
+
+// Score each column by the share of its non-empty sampled values that look
+// like US ZIP codes and return those scoring above `threshold`
+pub fn detect_zip_columns(
+    ctx: &CsvContext,
+    delimiter: u8,
+    sample_rows: usize,
+    threshold: f64,
+) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let sample = &data[..data.len().min(sample_rows)];
+
+    let mut found = Vec::new();
+    for (index, name) in header.iter().enumerate() {
+        let values: Vec<&str> = sample
+            .iter()
+            .filter_map(|row| row.get(index).map(|v| v.trim()))
+            .filter(|v| !v.is_empty())
+            .collect();
+        if values.is_empty() {
+            continue;
+        }
+        let score = values.iter().filter(|v| is_us_zip(v)).count() as f64 / values.len() as f64;
+        if score > threshold {
+            found.push((name.clone(), score));
+        }
+    }
+    Ok(found)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatReport {
+    pub valid: usize,
+    pub invalid: usize,
+    // (row, value) for every non-empty value that failed the check
+    pub violations: Vec<(usize, String)>,
+}
+
+// Run `is_valid` over the non-empty values of `column`
+fn validate_format<F>(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    is_valid: F,
+) -> Result<FormatReport, Box<dyn Error>>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut report = FormatReport::default();
+    for (row, value) in column_values(ctx, delimiter, column)? {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if is_valid(trimmed) {
+            report.valid += 1;
+        } else {
+            report.invalid += 1;
+            report.violations.push((row, value));
+        }
+    }
+    Ok(report)
+}
+
+// Check US ZIP and ZIP+4 format. Empty fields are skipped.
+pub fn validate_zip_codes(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<FormatReport, Box<dyn Error>> {
+    validate_format(ctx, delimiter, column, is_us_zip)
+}
+
+// Restore leading zeros lost when ZIP codes were stored as integers: all-digit
+// values of 1 to 4 characters are left-padded to 5. Returns fields padded.
+pub fn pad_zip_codes(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<usize, Box<dyn Error>> {
+    map_column(ctx, delimiter, column, |value| {
+        let value = value.trim();
+        ((1..=4).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| format!("{value:0>5}"))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SsnCheck {
+    Valid,
+    InvalidFormat,
+    InvalidValue,
+}
+
+// Accepts `ddd-dd-dddd` or nine bare digits, then applies the SSA rules:
+// area 000, 666, and 900-999, group 00, and serial 0000 are never issued
+fn check_ssn(value: &str) -> SsnCheck {
+    let digits: String = match value.len() {
+        11 if value.as_bytes()[3] == b'-' && value.as_bytes()[6] == b'-' => value.replace('-', ""),
+        9 => value.to_string(),
+        _ => return SsnCheck::InvalidFormat,
+    };
+    if digits.len() != 9 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return SsnCheck::InvalidFormat;
+    }
+    let (area, group, serial) = (&digits[..3], &digits[3..5], &digits[5..]);
+    if area == "000" || area == "666" || area.starts_with('9') || group == "00" || serial == "0000"
+    {
+        SsnCheck::InvalidValue
+    } else {
+        SsnCheck::Valid
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SsnReport {
+    pub valid: usize,
+    pub invalid: usize,
+    pub invalid_format: usize,
+    // Well formed but in a range the SSA never issues
+    pub invalid_value: usize,
+    pub violations: Vec<(usize, String)>,
+}
+
+pub fn validate_ssn(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<SsnReport, Box<dyn Error>> {
+    let mut report = SsnReport::default();
+    for (row, value) in column_values(ctx, delimiter, column)? {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match check_ssn(trimmed) {
+            SsnCheck::Valid => {
+                report.valid += 1;
+                continue;
+            }
+            SsnCheck::InvalidFormat => report.invalid_format += 1,
+            SsnCheck::InvalidValue => report.invalid_value += 1,
+        }
+        report.invalid += 1;
+        report.violations.push((row, value));
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CardType {
+    Visa,
+    Mastercard,
+    Amex,
+}
+
+fn luhn_valid(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let d = u32::from(b - b'0');
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// Match the major card number layouts after removing spaces and dashes, and
+// require a passing Luhn checksum
+fn card_type(value: &str) -> Option<CardType> {
+    let digits: String = value.chars().filter(|c| *c != ' ' && *c != '-').collect();
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let card = match (digits.len(), digits.as_bytes()) {
+        (13 | 16, [b'4', ..]) => CardType::Visa,
+        (16, [b'5', b'1'..=b'5', ..]) => CardType::Mastercard,
+        (15, [b'3', b'4' | b'7', ..]) => CardType::Amex,
+        _ => return None,
+    };
+    luhn_valid(&digits).then_some(card)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardDetection {
+    PiiDetected {
+        column: String,
+        card_type_distribution: BTreeMap<CardType, usize>,
+        sample_count: usize,
+    },
+    NoPiiDetected,
+}
+
+// Find the column where most sampled values are valid card numbers. A column
+// qualifies when more than half of its non-empty sampled values match.
+pub fn detect_credit_card_column(
+    ctx: &CsvContext,
+    delimiter: u8,
+    sample_rows: usize,
+) -> Result<CardDetection, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(CardDetection::NoPiiDetected);
+    };
+    let sample = &data[..data.len().min(sample_rows)];
+
+    let mut best: Option<(usize, BTreeMap<CardType, usize>, usize)> = None;
+    for index in 0..header.len() {
+        let values: Vec<&str> = sample
+            .iter()
+            .filter_map(|row| row.get(index).map(|v| v.trim()))
+            .filter(|v| !v.is_empty())
+            .collect();
+        let mut distribution = BTreeMap::new();
+        for card in values.iter().filter_map(|v| card_type(v)) {
+            *distribution.entry(card).or_insert(0) += 1;
+        }
+        let matches: usize = distribution.values().sum();
+        let best_matches = best.as_ref().map_or(0, |(_, d, _)| d.values().sum());
+        if matches * 2 > values.len() && matches > best_matches {
+            best = Some((index, distribution, values.len()));
+        }
+    }
+
+    Ok(match best {
+        Some((index, card_type_distribution, sample_count)) => CardDetection::PiiDetected {
+            column: header[index].clone(),
+            card_type_distribution,
+            sample_count,
+        },
+        None => CardDetection::NoPiiDetected,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+    Both,
+}
+
+fn is_allowed_ip(value: &str, allowed: IpVersion, allow_cidr: bool) -> bool {
+    let (address, prefix) = match value.split_once('/') {
+        Some((address, prefix)) if allow_cidr => (address, Some(prefix)),
+        Some(_) => return false,
+        None => (value, None),
+    };
+    let Ok(ip) = IpAddr::from_str(address) else {
+        return false;
+    };
+    let max_prefix = match (ip, allowed) {
+        (IpAddr::V4(_), IpVersion::V4 | IpVersion::Both) => 32,
+        (IpAddr::V6(_), IpVersion::V6 | IpVersion::Both) => 128,
+        _ => return false,
+    };
+    prefix.is_none_or(|p| p.parse::<u8>().is_ok_and(|p| p <= max_prefix))
+}
+
+// Check IP addresses in `column` against the allowed versions. CIDR notation
+// is rejected unless `allow_cidr` is set. Empty fields are skipped.
+pub fn validate_ip_addresses(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    allowed: IpVersion,
+    allow_cidr: bool,
+) -> Result<FormatReport, Box<dyn Error>> {
+    validate_format(ctx, delimiter, column, |value| {
+        is_allowed_ip(value, allowed, allow_cidr)
+    })
+}
+
+// Hyphenated, simple (32 hex digits), braced `{...}`, or `urn:uuid:` forms,
+// as accepted by most UUID libraries
+fn parse_uuid(value: &str) -> Option<u128> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("urn:uuid:")
+        .or_else(|| value.strip_prefix('{').and_then(|v| v.strip_suffix('}')))
+        .unwrap_or(value);
+    let hex = match value.len() {
+        32 => value.to_string(),
+        36 => {
+            let groups: Vec<&str> = value.split('-').collect();
+            let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+            if lengths != [8, 4, 4, 4, 12] {
+                return None;
+            }
+            groups.concat()
+        }
+        _ => return None,
+    };
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(&hex, 16).ok()
+}
+
+// Check UUIDs in `column`, restricted to one version number when `version`
+// is set. Empty fields are skipped.
+pub fn validate_uuids(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    version: Option<u8>,
+) -> Result<FormatReport, Box<dyn Error>> {
+    validate_format(ctx, delimiter, column, |value| {
+        parse_uuid(value)
+            .is_some_and(|uuid| version.is_none_or(|v| (uuid >> 76) & 0xF == u128::from(v)))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UuidFormat {
+    // 550e8400-e29b-41d4-a716-446655440000
+    Hyphenated,
+    // 550e8400e29b41d4a716446655440000
+    Simple,
+    // urn:uuid:550e8400-e29b-41d4-a716-446655440000
+    Urn,
+}
+
+fn format_uuid(uuid: u128, format: UuidFormat) -> String {
+    let hex = format!("{uuid:032x}");
+    let hyphenated = || {
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    };
+    match format {
+        UuidFormat::Hyphenated => hyphenated(),
+        UuidFormat::Simple => hex.clone(),
+        UuidFormat::Urn => format!("urn:uuid:{}", hyphenated()),
+    }
+}
+
+// Rewrite UUIDs in `column` in lowercase `format`. Values that cannot be
+// parsed are left unchanged. Returns (normalized, skipped), where normalized
+// counts only the fields that changed; empty fields count as neither.
+pub fn normalize_uuids(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    format: UuidFormat,
+) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut skipped = 0;
+    let normalized = map_column(ctx, delimiter, column, |value| {
+        if value.trim().is_empty() {
+            return None;
+        }
+        let Some(uuid) = parse_uuid(value) else {
+            skipped += 1;
+            return None;
+        };
+        let formatted = format_uuid(uuid, format);
+        (formatted != value).then_some(formatted)
+    })?;
+    Ok((normalized, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn phones_are_normalized_to_e164() {
+        let mut c =
+            ctx("phone\n(555) 234-5678\n+1-555-234-5678\n5552345678\n+15552345678\ncall me\n");
+        let result = normalize_phones(&mut c, b',', &0.into(), "us", PhoneFormat::E164).unwrap();
+        assert_eq!(result, (3, 1));
+        assert_eq!(
+            c.processed_text,
+            "phone\n+15552345678\n+15552345678\n+15552345678\n+15552345678\ncall me\n"
+        );
+    }
+
+    #[test]
+    fn national_and_international_formats() {
+        assert_eq!(
+            format_phone("555.234.5678", "US", PhoneFormat::National).as_deref(),
+            Some("(555) 234-5678")
+        );
+        assert_eq!(
+            format_phone("+15552345678", "GB", PhoneFormat::International).as_deref(),
+            Some("+1 555-234-5678")
+        );
+        assert_eq!(
+            format_phone("+44 20 7946 0958", "GB", PhoneFormat::E164).as_deref(),
+            Some("+442079460958")
+        );
+        assert_eq!(format_phone("15552345678", "GB", PhoneFormat::E164), None);
+        assert_eq!(format_phone("5552345678", "GB", PhoneFormat::E164), None);
+        // Area code or exchange starting with 0 or 1
+        assert_eq!(format_phone("055-234-5678", "US", PhoneFormat::E164), None);
+        assert_eq!(format_phone("555-123-4567", "CA", PhoneFormat::E164), None);
+    }
+
+    #[test]
+    fn zip_columns_are_scored() {
+        let c = ctx("id,zip,city\n10001,02139,Cambridge\n2,94103-1234,SF\n3,n/a,LA\n");
+        let found = detect_zip_columns(&c, b',', 10, 0.5).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "zip");
+        assert!((found[0].1 - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zip_codes_are_validated() {
+        let c = ctx("zip\n02139\n94103-1234\n2139\n\"\"\n9410-31234\n");
+        let report = validate_zip_codes(&c, b',', &0.into()).unwrap();
+        assert_eq!(report.valid, 2);
+        assert_eq!(report.invalid, 2);
+        assert_eq!(report.violations[0], (3, "2139".to_string()));
+    }
+
+    #[test]
+    fn short_zip_codes_are_padded() {
+        let mut c = ctx("zip\n2139\n501\n94103\nabc\n");
+        assert_eq!(pad_zip_codes(&mut c, b',', &0.into()).unwrap(), 2);
+        assert_eq!(c.processed_text, "zip\n02139\n00501\n94103\nabc\n");
+    }
+
+    #[test]
+    fn ssns_are_checked_for_format_and_value() {
+        let c = ctx("ssn\n123-45-6789\n123456789\n666-12-3456\n123-00-4567\n12-345-6789\n");
+        let report = validate_ssn(&c, b',', &0.into()).unwrap();
+        assert_eq!(report.valid, 2);
+        assert_eq!(report.invalid, 3);
+        assert_eq!(report.invalid_value, 2);
+        assert_eq!(report.invalid_format, 1);
+        assert_eq!(report.violations[2], (5, "12-345-6789".to_string()));
+    }
+
+    #[test]
+    fn credit_card_column_is_detected() {
+        let c = ctx(
+            "id,card\n1,4111 1111 1111 1111\n2,5500-0000-0000-0004\n3,3782 822463 10005\n4,1234\n",
+        );
+        let detection = detect_credit_card_column(&c, b',', 10).unwrap();
+        let CardDetection::PiiDetected {
+            column,
+            card_type_distribution,
+            sample_count,
+        } = detection
+        else {
+            panic!("expected a card column");
+        };
+        assert_eq!(column, "card");
+        assert_eq!(sample_count, 4);
+        assert_eq!(card_type_distribution[&CardType::Amex], 1);
+        assert_eq!(card_type_distribution.len(), 3);
+    }
+
+    #[test]
+    fn luhn_failures_are_not_cards() {
+        assert_eq!(card_type("4111111111111112"), None);
+        let c = ctx("card\n4111111111111112\n");
+        assert_eq!(
+            detect_credit_card_column(&c, b',', 10).unwrap(),
+            CardDetection::NoPiiDetected
+        );
+    }
+
+    #[test]
+    fn ip_addresses_are_filtered_by_version() {
+        let c = ctx("ip\n10.0.0.1\n::1\n10.0.0.0/8\n300.1.1.1\n");
+        let v4 = validate_ip_addresses(&c, b',', &0.into(), IpVersion::V4, false).unwrap();
+        assert_eq!((v4.valid, v4.invalid), (1, 3));
+        let both = validate_ip_addresses(&c, b',', &0.into(), IpVersion::Both, true).unwrap();
+        assert_eq!((both.valid, both.invalid), (3, 1));
+        assert_eq!(both.violations, vec![(4, "300.1.1.1".to_string())]);
+    }
+
+    #[test]
+    fn uuids_are_validated_by_version() {
+        let c = ctx(
+            "id\n550e8400-e29b-41d4-a716-446655440000\n6BA7B8109DAD11D180B400C04FD430C8\n\
+             {550e8400-e29b-41d4-a716-446655440000}\n550e8400-e29b-41d4-a716-44665544000g\n",
+        );
+        let any = validate_uuids(&c, b',', &0.into(), None).unwrap();
+        assert_eq!((any.valid, any.invalid), (3, 1));
+
+        let v4 = validate_uuids(&c, b',', &0.into(), Some(4)).unwrap();
+        assert_eq!((v4.valid, v4.invalid), (2, 2));
+        assert_eq!(
+            v4.violations[0],
+            (2, "6BA7B8109DAD11D180B400C04FD430C8".to_string())
+        );
+    }
+
+    #[test]
+    fn uuids_are_normalized() {
+        let mut c = ctx(
+            "id\n{550E8400-E29B-41D4-A716-446655440000}\n550e8400e29b41d4a716446655440000\nnope\n",
+        );
+        let result = normalize_uuids(&mut c, b',', &0.into(), UuidFormat::Urn).unwrap();
+        assert_eq!(result, (2, 1));
+        assert_eq!(
+            c.processed_text,
+            "id\nurn:uuid:550e8400-e29b-41d4-a716-446655440000\n\
+             urn:uuid:550e8400-e29b-41d4-a716-446655440000\nnope\n"
+        );
+        let result = normalize_uuids(&mut c, b',', &0.into(), UuidFormat::Urn).unwrap();
+        assert_eq!(result, (0, 1));
+        normalize_uuids(&mut c, b',', &0.into(), UuidFormat::Simple).unwrap();
+        assert!(
+            c.processed_text
+                .contains("\n550e8400e29b41d4a716446655440000\n")
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::error::Error;
+
+use crate::csv_io::write_rows;
+use crate::datetime::{DateTime, Format};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Integer,
+    Float,
+    String,
+    Boolean,
+    Email,
+    Date,
+    Uuid,
+}
+
+// `min` and `max` bound integers and floats, and are years for dates.
+// `length` is the character count of strings and of an email's local part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub kind: FieldKind,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub length: Option<usize>,
+}
+
+impl ColumnSpec {
+    pub fn new(name: &str, kind: FieldKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            min: None,
+            max: None,
+            length: None,
+        }
+    }
+}
+
+// SplitMix64: tiny, fast, and fully determined by the seed
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in [low, high]
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        let span = (i128::from(high) - i128::from(low) + 1) as u128;
+        (i128::from(low) + (u128::from(self.next_u64()) % span) as i128) as i64
+    }
+
+    fn letters(&mut self, length: usize) -> String {
+        (0..length)
+            .map(|_| (b'a' + (self.next_u64() % 26) as u8) as char)
+            .collect()
+    }
+}
+
+fn bounds(spec: &ColumnSpec, min: f64, max: f64) -> Result<(f64, f64), Box<dyn Error>> {
+    let (min, max) = (spec.min.unwrap_or(min), spec.max.unwrap_or(max));
+    if !min.is_finite() || !max.is_finite() || min > max {
+        return Err(format!("{}: invalid bounds {min} to {max}", spec.name).into());
+    }
+    Ok((min, max))
+}
+
+fn generate_value(
+    spec: &ColumnSpec,
+    rng: &mut Rng,
+    date_format: &Format,
+) -> Result<String, Box<dyn Error>> {
+    let value = match spec.kind {
+        FieldKind::Integer => {
+            let (min, max) = bounds(spec, 0.0, 1000.0)?;
+            let (low, high) = (min.ceil() as i64, max.floor() as i64);
+            if low > high {
+                return Err(format!("{}: no integer between {min} and {max}", spec.name).into());
+            }
+            rng.range(low, high).to_string()
+        }
+        FieldKind::Float => {
+            let (min, max) = bounds(spec, 0.0, 1.0)?;
+            let value = min + rng.next_f64() * (max - min);
+            format!("{value:.4}")
+        }
+        FieldKind::String => rng.letters(spec.length.unwrap_or(8)),
+        FieldKind::Boolean => (rng.next_u64() & 1 == 1).to_string(),
+        FieldKind::Email => format!("{}@example.com", rng.letters(spec.length.unwrap_or(8))),
+        FieldKind::Date => {
+            let (min, max) = bounds(spec, 2000.0, 2029.0)?;
+            let first = date_format
+                .parse(&format!("{:04}-01-01", min as i64))
+                .ok_or("date bounds out of range")?;
+            let last = date_format
+                .parse(&format!("{:04}-12-31", max as i64))
+                .ok_or("date bounds out of range")?;
+            let day = 86_400_000_000_000;
+            let days = rng.range(
+                (first.epoch_nanos() / day) as i64,
+                (last.epoch_nanos() / day) as i64,
+            );
+            date_format.format(&DateTime::from_epoch_nanos(i128::from(days) * day, 0))
+        }
+        FieldKind::Uuid => {
+            // Random version 4, RFC 4122 variant
+            let high = (rng.next_u64() & !0xF000) | 0x4000;
+            let low = (rng.next_u64() & !(0b11 << 62)) | (0b10 << 62);
+            format!(
+                "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                high >> 32,
+                (high >> 16) & 0xFFFF,
+                high & 0xFFFF,
+                low >> 48,
+                low & 0xFFFF_FFFF_FFFF
+            )
+        }
+    };
+    Ok(value)
+}
+
+// Build a CSV with a header from `specs` and `row_count` rows of random
+// values. The same seed always produces the same file.
+pub fn generate_test_csv(
+    specs: &[ColumnSpec],
+    row_count: usize,
+    seed: u64,
+) -> Result<String, Box<dyn Error>> {
+    let mut rng = Rng(seed);
+    let date_format = Format::new("%F")?;
+    let mut rows = Vec::with_capacity(row_count + 1);
+    rows.push(specs.iter().map(|spec| spec.name.clone()).collect());
+    for _ in 0..row_count {
+        let row = specs
+            .iter()
+            .map(|spec| generate_value(spec, &mut rng, &date_format))
+            .collect::<Result<Vec<String>, _>>()?;
+        rows.push(row);
+    }
+    write_rows(&rows, b',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::CsvContext;
+    use crate::csv_io::read_rows;
+    use crate::schema::{ColumnType, infer_schema};
+
+    #[test]
+    fn generated_csv_is_seeded_and_typed() {
+        let mut age = ColumnSpec::new("age", FieldKind::Integer);
+        age.min = Some(18.0);
+        age.max = Some(20.0);
+        let specs = [
+            age,
+            ColumnSpec::new("score", FieldKind::Float),
+            ColumnSpec::new("active", FieldKind::Boolean),
+            ColumnSpec::new("email", FieldKind::Email),
+            ColumnSpec::new("born", FieldKind::Date),
+            ColumnSpec::new("id", FieldKind::Uuid),
+        ];
+        let csv = generate_test_csv(&specs, 50, 7).unwrap();
+        assert_eq!(csv, generate_test_csv(&specs, 50, 7).unwrap());
+        assert_ne!(csv, generate_test_csv(&specs, 50, 8).unwrap());
+
+        let rows = read_rows(&csv, b',').unwrap();
+        assert_eq!(rows.len(), 51);
+        assert!(rows[1..].iter().all(|row| {
+            (18..=20).contains(&row[0].parse::<i64>().unwrap())
+                && row[3].ends_with("@example.com")
+                && row[5].len() == 36
+                && row[5].as_bytes()[14] == b'4'
+        }));
+        let schema = infer_schema(&CsvContext::new(csv.into_bytes()), b',', 50).unwrap();
+        let types: Vec<ColumnType> = schema.iter().map(|c| c.column_type).collect();
+        assert_eq!(
+            types[..3],
+            [ColumnType::Integer, ColumnType::Number, ColumnType::Boolean]
+        );
+
+        let mut bad = ColumnSpec::new("n", FieldKind::Integer);
+        bad.min = Some(5.0);
+        bad.max = Some(1.0);
+        assert!(generate_test_csv(&[bad], 1, 0).is_err());
+    }
+}
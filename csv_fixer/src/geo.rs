@@ -0,0 +1,236 @@
+use std::error::Error;
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+use crate::transform::map_column;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoordinateReport {
+    pub valid: usize,
+    pub invalid: usize,
+    pub unparseable: usize,
+    // (row, lat, lng) for every pair outside [-90, 90] x [-180, 180]
+    pub violations: Vec<(usize, f64, f64)>,
+}
+
+fn parse_coordinate(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+// Check latitude and longitude ranges row by row. Rows where both fields are
+// empty are skipped; a pair with a missing or non-numeric side is unparseable.
+pub fn validate_lat_lng(
+    ctx: &CsvContext,
+    delimiter: u8,
+    lat_col: &Column,
+    lng_col: &Column,
+) -> Result<CoordinateReport, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut report = CoordinateReport::default();
+    let Some(header) = rows.first() else {
+        return Ok(report);
+    };
+    let (lat_index, lng_index) = (lat_col.resolve(header)?, lng_col.resolve(header)?);
+
+    for (row, fields) in rows.iter().enumerate().skip(1) {
+        let lat = fields.get(lat_index).map_or("", String::as_str);
+        let lng = fields.get(lng_index).map_or("", String::as_str);
+        if lat.trim().is_empty() && lng.trim().is_empty() {
+            continue;
+        }
+        let (Some(lat), Some(lng)) = (parse_coordinate(lat), parse_coordinate(lng)) else {
+            report.unparseable += 1;
+            continue;
+        };
+        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) {
+            report.valid += 1;
+        } else {
+            report.invalid += 1;
+            report.violations.push((row, lat, lng));
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatLngSwap {
+    Swapped { rows_fixed: usize },
+    NoSwapNeeded,
+}
+
+// Swap the two columns' values in every data row when `lat_col` holds values
+// only valid as longitudes (beyond +-90) while every `lng_col` value would be
+// a valid latitude. Header names are kept in place.
+pub fn detect_lat_lng_swap(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    lat_col: &Column,
+    lng_col: &Column,
+) -> Result<LatLngSwap, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(LatLngSwap::NoSwapNeeded);
+    };
+    let (lat_index, lng_index) = (lat_col.resolve(header)?, lng_col.resolve(header)?);
+    let parsed = |index: usize| {
+        rows.iter()
+            .skip(1)
+            .filter_map(move |row| row.get(index).and_then(|v| parse_coordinate(v)))
+    };
+
+    let lat_is_longitude =
+        parsed(lat_index).any(|n| n.abs() > 90.0) && parsed(lat_index).all(|n| n.abs() <= 180.0);
+    let lng_is_latitude = parsed(lng_index).all(|n| n.abs() <= 90.0);
+    if !lat_is_longitude || !lng_is_latitude {
+        return Ok(LatLngSwap::NoSwapNeeded);
+    }
+
+    let mut rows_fixed = 0;
+    // Short rows missing either field are left alone
+    for row in rows.iter_mut().skip(1) {
+        if row.len() > lat_index.max(lng_index) && row[lat_index] != row[lng_index] {
+            row.swap(lat_index, lng_index);
+            rows_fixed += 1;
+        }
+    }
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(LatLngSwap::Swapped { rows_fixed })
+}
+
+// Unsigned decimal number at the start of `input`
+fn take_number(input: &mut &str) -> Option<f64> {
+    let len = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, rest) = input.split_at(len);
+    let number = number.parse().ok()?;
+    *input = rest;
+    Some(number)
+}
+
+// Strip the first of `markers` found at the start of `input`
+fn take_marker(input: &mut &str, markers: &[&str]) -> bool {
+    match markers.iter().find_map(|marker| input.strip_prefix(marker)) {
+        Some(rest) => {
+            *input = rest.trim_start();
+            true
+        }
+        None => false,
+    }
+}
+
+// One coordinate such as `40°26'46"N`, `40d 26m 46s N` or `-79°58.9'`.
+// Minutes and seconds are optional; a degree marker is required.
+fn take_dms(input: &mut &str) -> Option<f64> {
+    let negative = take_marker(input, &["-"]);
+    let degrees = take_number(input)?;
+    if !take_marker(input, &["°", "º", "d", "D"]) {
+        return None;
+    }
+    let mut minutes = 0.0;
+    let mut seconds = 0.0;
+    if input.starts_with(|c: char| c.is_ascii_digit()) {
+        minutes = take_number(input)?;
+        if !take_marker(input, &["'", "′", "m"]) {
+            return None;
+        }
+        if input.starts_with(|c: char| c.is_ascii_digit()) {
+            seconds = take_number(input)?;
+            if !take_marker(input, &["\"", "″", "''", "s"]) {
+                return None;
+            }
+        }
+    }
+    if degrees > 180.0 || minutes >= 60.0 || seconds >= 60.0 {
+        return None;
+    }
+
+    let hemisphere = input.chars().next().map(|c| c.to_ascii_uppercase());
+    let south_or_west = match hemisphere {
+        Some(h @ ('N' | 'S' | 'E' | 'W')) => {
+            *input = input[1..].trim_start();
+            h == 'S' || h == 'W'
+        }
+        _ => false,
+    };
+    let value = degrees + minutes / 60.0 + seconds / 3600.0;
+    Some(if negative != south_or_west {
+        -value
+    } else {
+        value
+    })
+}
+
+// Six decimal places (about 0.1 m) without trailing zeros
+fn format_degrees(value: f64) -> String {
+    let formatted = format!("{value:.6}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+// Convert degrees-minutes-seconds values in `column` to decimal degrees,
+// negative for S and W. A field holding a lat/lng pair is converted to both
+// values separated by a space. Returns the fields converted.
+pub fn dms_to_decimal(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<usize, Box<dyn Error>> {
+    map_column(ctx, delimiter, column, |value| {
+        let mut input = value.trim();
+        let mut converted = vec![format_degrees(take_dms(&mut input)?)];
+        take_marker(&mut input, &[","]);
+        if !input.is_empty() {
+            converted.push(format_degrees(take_dms(&mut input)?));
+        }
+        input.is_empty().then(|| converted.join(" "))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn coordinates_are_range_checked() {
+        let c = ctx("lat,lng\n40.4,-79.9\n91,10\n,\nx,1\n-45,181\n");
+        let report = validate_lat_lng(&c, b',', &"lat".into(), &"lng".into()).unwrap();
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.invalid, 2);
+        assert_eq!(report.unparseable, 1);
+        assert_eq!(report.violations, vec![(2, 91.0, 10.0), (5, -45.0, 181.0)]);
+    }
+
+    #[test]
+    fn swapped_coordinates_are_fixed() {
+        let mut c = ctx("id,lat,lng\n1,-79.9,40.4\n2,151.2,-33.9\n3,,\n");
+        let swap = detect_lat_lng_swap(&mut c, b',', &"lat".into(), &"lng".into()).unwrap();
+        assert_eq!(swap, LatLngSwap::Swapped { rows_fixed: 2 });
+        assert_eq!(
+            c.processed_text,
+            "id,lat,lng\n1,40.4,-79.9\n2,-33.9,151.2\n3,,\n"
+        );
+
+        let swap = detect_lat_lng_swap(&mut c, b',', &"lat".into(), &"lng".into()).unwrap();
+        assert_eq!(swap, LatLngSwap::NoSwapNeeded);
+    }
+
+    #[test]
+    fn dms_values_are_converted() {
+        let mut c = ctx(
+            "pos\n\"40°26'46\"\"N 79°58'56\"\"W\"\n33d 52m 10s S\n-12.5\n151°12.5'E\n40°26'N x\n",
+        );
+        assert_eq!(dms_to_decimal(&mut c, b',', &0.into()).unwrap(), 3);
+        assert_eq!(
+            c.processed_text,
+            "pos\n40.446111 -79.982222\n-33.869444\n-12.5\n151.208333\n40°26'N x\n"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::error::Error;
+
+use crate::context::CsvContext;
+use crate::csv_io::write_rows;
+
+// Resolve the five predefined entities and numeric character references
+fn xml_unescape(text: &str) -> Result<String, Box<dyn Error>> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or("unterminated entity reference")?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = match entity.strip_prefix("#x") {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                };
+                code.and_then(char::from_u32)
+                    .ok_or(format!("unknown entity: &{entity};"))?
+            }
+        };
+        out.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// Name without any namespace prefix
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+// Offset of the `>` closing the tag at the start of `input`, skipping any
+// inside quoted attribute values
+fn tag_end(input: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            (Some(open), _) if c == open => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug)]
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, String)>,
+    closing: bool,
+    self_closing: bool,
+}
+
+// Parse the inside of `<...>`
+fn parse_tag(inner: &str) -> Result<Tag<'_>, Box<dyn Error>> {
+    let closing = inner.starts_with('/');
+    let self_closing = inner.ends_with('/');
+    let inner = inner.trim_start_matches('/').trim_end_matches('/').trim();
+    let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+    let (name, mut rest) = inner.split_at(name_end);
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let eq = rest
+            .find('=')
+            .ok_or(format!("malformed attribute in <{inner}>"))?;
+        let key = rest[..eq].trim();
+        rest = rest[eq + 1..].trim_start();
+        let quote = rest
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or(format!("unquoted attribute in <{inner}>"))?;
+        let close = rest[1..]
+            .find(quote)
+            .ok_or(format!("unterminated attribute in <{inner}>"))?;
+        attributes.push((key, xml_unescape(&rest[1..close + 1])?));
+        rest = &rest[close + 2..];
+    }
+    Ok(Tag {
+        name,
+        attributes,
+        closing,
+        self_closing,
+    })
+}
+
+// Build a CSV from the repeated elements matched by `row_path` (`a/b/row`,
+// `//row`; only the last step is used). Row fields are read from attributes
+// and from child elements' text, keyed by `field_names`; missing fields are
+// empty. Only flat documents are supported: comments and processing
+// instructions are skipped, and CDATA sections are rejected rather than lost.
+pub fn from_xml(
+    xml: &str,
+    row_path: &str,
+    field_names: &[&str],
+) -> Result<CsvContext, Box<dyn Error>> {
+    let row_name = row_path
+        .rsplit('/')
+        .find(|step| !step.is_empty())
+        .ok_or("empty row path")?;
+    let header: Vec<String> = field_names.iter().map(|name| name.to_string()).collect();
+    let mut rows = vec![header];
+
+    let mut row: Option<Vec<String>> = None;
+    let mut field: Option<(usize, String)> = None;
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        if let Some((_, text)) = field.as_mut() {
+            text.push_str(&xml_unescape(&rest[..open])?);
+        }
+        rest = &rest[open..];
+        if rest.starts_with("<![CDATA[") {
+            return Err("CDATA sections are not supported".into());
+        }
+        let skip_to = if rest.starts_with("<!--") {
+            Some("-->")
+        } else if rest.starts_with("<?") {
+            Some("?>")
+        } else if rest.starts_with("<!") {
+            Some(">")
+        } else {
+            None
+        };
+        if let Some(terminator) = skip_to {
+            let end = rest.find(terminator).ok_or("unterminated markup")?;
+            rest = &rest[end + terminator.len()..];
+            continue;
+        }
+
+        let close = tag_end(rest).ok_or("unterminated tag")?;
+        let tag = parse_tag(&rest[1..close])?;
+        rest = &rest[close + 1..];
+        let name = local_name(tag.name);
+
+        if name == row_name && !tag.closing {
+            let mut values = vec![String::new(); field_names.len()];
+            for (key, value) in tag.attributes {
+                if let Some(i) = field_names.iter().position(|f| *f == local_name(key)) {
+                    values[i] = value;
+                }
+            }
+            if tag.self_closing {
+                rows.push(values);
+            } else {
+                row = Some(values);
+            }
+        } else if name == row_name {
+            rows.extend(row.take());
+        } else if let Some(values) = row.as_mut() {
+            if tag.closing {
+                if let Some((i, text)) = field.take() {
+                    values[i] = text;
+                }
+            } else if field.is_some() {
+                return Err(format!("nested element <{name}> is not supported").into());
+            } else if !tag.self_closing
+                && let Some(i) = field_names.iter().position(|f| *f == name)
+            {
+                field = Some((i, String::new()));
+            }
+        }
+    }
+
+    Ok(CsvContext::new(write_rows(&rows, b',')?.into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_rows_become_csv() {
+        let xml = "<?xml version=\"1.0\"?>\n<!-- export -->\n<people>\
+                   <person id=\"1\"><name>A &amp; B</name><note/></person>\
+                   <person id=\"2\" name=\"C, D\"/>\
+                   <ns:person><ns:name>&#233;</ns:name><extra>x</extra></ns:person>\
+                   </people>";
+        let c = from_xml(xml, "people/person", &["id", "name"]).unwrap();
+        assert_eq!(c.processed_text, "id,name\n1,A & B\n2,\"C, D\"\n,é\n");
+    }
+
+    #[test]
+    fn nested_fields_are_rejected() {
+        let xml = "<r><row><name><b>x</b></name></row></r>";
+        assert!(from_xml(xml, "//row", &["name"]).is_err());
+    }
+
+    #[test]
+    fn cdata_is_rejected_and_quoted_gt_is_kept() {
+        let xml = "<r><row><name><![CDATA[a > b]]></name></row></r>";
+        assert!(from_xml(xml, "//row", &["name"]).is_err());
+
+        let xml = "<r><row expr=\"a > b\" id='1'/></r>";
+        let c = from_xml(xml, "//row", &["expr", "id"]).unwrap();
+        assert_eq!(c.processed_text, "expr,id\na > b,1\n");
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::csv_io::{read_rows, write_rows};
+
+type Table = (Vec<String>, Vec<Vec<String>>);
+
+fn parse_table(bytes: &[u8], delimiter: u8) -> Result<Table, Box<dyn Error>> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut rows = read_rows(&text, delimiter)?;
+    if rows.is_empty() {
+        return Err("input has no header row".into());
+    }
+    let header = rows.remove(0);
+    // Pad short rows so the right side of a joined row stays aligned
+    for row in rows.iter_mut() {
+        if row.len() < header.len() {
+            row.resize(header.len(), String::new());
+        }
+    }
+    Ok((header, rows))
+}
+
+// Concatenate two headers, suffixing names found on both sides
+fn merge_headers(left: &[String], right: &[String]) -> Vec<String> {
+    let left_names = left.iter().map(|name| {
+        if right.contains(name) {
+            format!("{name}_left")
+        } else {
+            name.clone()
+        }
+    });
+    let right_names = right.iter().map(|name| {
+        if left.contains(name) {
+            format!("{name}_right")
+        } else {
+            name.clone()
+        }
+    });
+    left_names.chain(right_names).collect()
+}
+
+// Cartesian product of the data rows of two CSVs. The output is comma
+// delimited with a merged header.
+pub fn cross_join(
+    left: &[u8],
+    right: &[u8],
+    left_delimiter: u8,
+    right_delimiter: u8,
+) -> Result<String, Box<dyn Error>> {
+    let (left_header, left_rows) = parse_table(left, left_delimiter)?;
+    let (right_header, right_rows) = parse_table(right, right_delimiter)?;
+
+    let mut out = Vec::with_capacity(left_rows.len() * right_rows.len() + 1);
+    out.push(merge_headers(&left_header, &right_header));
+    for l in &left_rows {
+        for r in &right_rows {
+            out.push(l.iter().chain(r).cloned().collect());
+        }
+    }
+    write_rows(&out, b',')
+}
+
+fn key_index(header: &[String], key: &str) -> Result<usize, Box<dyn Error>> {
+    header
+        .iter()
+        .position(|name| name == key)
+        .ok_or_else(|| format!("join key not found: {key}").into())
+}
+
+// Hash join on `left_key = right_key`. The output holds every left column
+// followed by the right columns minus the right key, comma delimited.
+pub fn inner_join(
+    left: &[u8],
+    right: &[u8],
+    left_key: &str,
+    right_key: &str,
+    left_delimiter: u8,
+    right_delimiter: u8,
+) -> Result<String, Box<dyn Error>> {
+    let (left_header, left_rows) = parse_table(left, left_delimiter)?;
+    let (mut right_header, right_rows) = parse_table(right, right_delimiter)?;
+    let left_index = key_index(&left_header, left_key)?;
+    let right_index = key_index(&right_header, right_key)?;
+    right_header.remove(right_index);
+
+    let mut lookup: HashMap<&str, Vec<&Vec<String>>> = HashMap::new();
+    for row in &right_rows {
+        lookup
+            .entry(row[right_index].as_str())
+            .or_default()
+            .push(row);
+    }
+
+    let mut out = vec![merge_headers(&left_header, &right_header)];
+    for l in &left_rows {
+        let Some(matches) = lookup.get(l[left_index].as_str()) else {
+            continue;
+        };
+        for r in matches {
+            let right_fields = r
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != right_index)
+                .map(|(_, field)| field);
+            out.push(l.iter().chain(right_fields).cloned().collect());
+        }
+    }
+    write_rows(&out, b',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_join_pairs_every_row() {
+        let out = cross_join(b"size,id\nS,7\nL\n", b"color;id\nred;1\n", b',', b';').unwrap();
+        assert_eq!(out, "size,id_left,color,id_right\nS,7,red,1\nL,,red,1\n");
+    }
+
+    #[test]
+    fn inner_join_matches_on_key() {
+        let left = b"id,name\n1,Ada\n2,Alan\n3,Grace\n";
+        let right = b"user_id\tname\n1\tadmin\n3\tdev\n3\tops\n";
+        let out = inner_join(left, right, "id", "user_id", b',', b'\t').unwrap();
+        assert_eq!(
+            out,
+            "id,name_left,name_right\n1,Ada,admin\n3,Grace,dev\n3,Grace,ops\n"
+        );
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod bom;
+pub mod builder;
+pub mod checksum;
+pub mod column;
+pub mod context;
+pub mod csv_io;
+pub mod datetime;
+pub mod detect;
+pub mod encoding;
+pub mod export;
+pub mod formats;
+pub mod generate;
+pub mod geo;
+pub mod import;
+pub mod join;
+pub mod repair;
+pub mod schema;
+pub mod sql;
+pub mod stats;
+pub mod timeout;
+pub mod transform;
+pub mod validate;
+pub mod watch;
+
+pub use builder::CsvRepairBuilder;
+pub use column::{Column, ColumnSelection};
+pub use context::CsvContext;
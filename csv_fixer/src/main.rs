@@ -1,11 +1,28 @@
 use std::env;
 use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufReader};
-use std::path::PathBuf;
-use walkdir::WalkDir;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::process::ExitCode;
 
-fn main() {
+use csv_fixer::encoding::decode_with_label;
+use csv_fixer::export::write_to_stdout;
+use csv_fixer::repair::RepairReport;
+use csv_fixer::watch::index_files;
+use csv_fixer::{CsvContext, CsvRepairBuilder};
+
+const USAGE: &str = "usage: csv_fixer repair --input <path> [--output <path>] [--encoding <label>] \
+[--delimiter <char>] [--strip-bom] [--normalize-line-endings] [--trim-fields]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("repair") {
+        return run_repair(&args[1..]);
+    }
+    explore();
+    ExitCode::SUCCESS
+}
+
+fn explore() {
     let start_dir = "test_csvs";
     let base_path = "test_csvs/test1.csv";
     // let current_dir = env::current_dir();
@@ -30,35 +47,6 @@ fn main() {
     println!("Files: {files:?}");
 }
 
-fn index_files(start_dir: &str) -> io::Result<Vec<PathBuf>> {
-    // Create a container for data
-    let mut files: Vec<PathBuf> = Vec::new();
-
-    for entry_result in WalkDir::new(start_dir) {
-        let entry = match entry_result {
-            Ok(entry) => entry,
-            Err(err) => {
-                eprintln!("Error reading directory entry: {err}");
-                continue; // Skip problematic entries
-            }
-        };
-
-        let metadata = match entry.metadata() {
-            Ok(metadata) => metadata,
-            Err(err) => {
-                eprintln!("Error getting metadata for entry: {err}");
-                continue;
-            }
-        };
-
-        if metadata.is_file() {
-            let file_path = entry.path().to_path_buf();
-            files.push(file_path);
-        }
-    }
-    Ok(files)
-}
-
 // Approach #1: Use CSV to validate
 // Notes:
 // - https://docs.rs/csv/1.1.6/csv/
@@ -78,3 +66,108 @@ fn validate_with_csv(file_path: &str) -> Result<(), Box<dyn Error>> {
     println!("File is valid.");
     Ok(())
 }
+
+#[derive(Debug, Default)]
+struct RepairArgs {
+    input: String,
+    output: Option<String>,
+    encoding: Option<String>,
+    delimiter: u8,
+    strip_bom: bool,
+    normalize_line_endings: bool,
+    trim_fields: bool,
+}
+
+fn parse_repair_args(args: &[String]) -> Result<RepairArgs, String> {
+    let mut parsed = RepairArgs {
+        delimiter: b',',
+        ..RepairArgs::default()
+    };
+    let mut input = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .cloned()
+                .ok_or(format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--input" => input = Some(value()?),
+            "--output" => parsed.output = Some(value()?),
+            "--encoding" => parsed.encoding = Some(value()?),
+            "--delimiter" => {
+                let delimiter = value()?;
+                parsed.delimiter = match delimiter.as_bytes() {
+                    [b] => *b,
+                    _ if delimiter == "\\t" => b'\t',
+                    _ => return Err(format!("delimiter must be one byte: {delimiter}")),
+                };
+            }
+            "--strip-bom" => parsed.strip_bom = true,
+            "--normalize-line-endings" => parsed.normalize_line_endings = true,
+            "--trim-fields" => parsed.trim_fields = true,
+            _ => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+    parsed.input = input.ok_or("--input is required")?;
+    Ok(parsed)
+}
+
+// Exit codes: 0 every requested repair applied (or nothing needed repair),
+// 1 partial repair with at least one step skipped, 2 unrecoverable error
+fn run_repair(args: &[String]) -> ExitCode {
+    let args = match parse_repair_args(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match repair_file(&args) {
+        Ok(report) => {
+            for event in &report.events {
+                eprintln!("{event}");
+            }
+            for skipped in &report.skipped {
+                eprintln!("skipped {skipped}");
+            }
+            if report.skipped.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(err) => {
+            eprintln!("Repair Error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn repair_file(args: &RepairArgs) -> Result<RepairReport, Box<dyn Error>> {
+    let mut ctx = CsvContext::from_path(&args.input)?;
+    if let Some(label) = &args.encoding {
+        ctx.processed_text = decode_with_label(&ctx.raw_data, label)?;
+    }
+
+    let mut builder = CsvRepairBuilder::from_context(ctx);
+    if args.strip_bom {
+        builder = builder.strip_bom();
+    }
+    if args.normalize_line_endings {
+        builder = builder.normalize_line_endings();
+    }
+    if args.trim_fields {
+        builder = builder.trim_fields(args.delimiter);
+    }
+    let (ctx, report) = builder.build_context();
+
+    match &args.output {
+        Some(path) => fs::write(path, &ctx.processed_text)?,
+        None => {
+            write_to_stdout(&ctx)?;
+        }
+    }
+    Ok(report)
+}
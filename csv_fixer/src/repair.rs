@@ -0,0 +1,774 @@
+use std::error::Error;
+
+use crate::builder::CsvRepairBuilder;
+use crate::column::{Column, ColumnSelection};
+use crate::context::CsvContext;
+use crate::csv_io::{needs_quotes, read_rows, split_records, write_rows, write_rows_with_quoting};
+use crate::detect::is_zero_byte_row;
+use crate::transform::map_column;
+use crate::validate::is_null_value;
+
+// Replace `\r\n` or a lone `\r` found inside quoted fields with `substitute`.
+// Row separators outside quotes are left alone; line ending normalization
+// is a separate step. Returns the number of fields that were modified.
+pub fn normalize_crlf_in_quoted(ctx: &mut CsvContext, delimiter: u8, substitute: &str) -> usize {
+    replace_quoted_line_breaks(ctx, delimiter, substitute, false)
+}
+
+// Replace every line break (`\n`, `\r\n`, or `\r`) inside quoted fields with
+// `replacement`. Fields stay quoted. Returns the number of fields modified.
+pub fn strip_field_newlines(ctx: &mut CsvContext, delimiter: u8, replacement: &str) -> usize {
+    replace_quoted_line_breaks(ctx, delimiter, replacement, true)
+}
+
+fn replace_quoted_line_breaks(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    substitute: &str,
+    include_lf: bool,
+) -> usize {
+    let delimiter = delimiter as char;
+    let mut out = String::with_capacity(ctx.processed_text.len());
+    let mut chars = ctx.processed_text.chars().peekable();
+    let mut in_quotes = false;
+    let mut field_modified = false;
+    let mut fields_modified = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                // An escaped quote ("") toggles twice, so the state stays correct
+                in_quotes = !in_quotes;
+                out.push(c);
+            }
+            '\r' if in_quotes => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                out.push_str(substitute);
+                field_modified = true;
+            }
+            '\n' if in_quotes && include_lf => {
+                out.push_str(substitute);
+                field_modified = true;
+            }
+            c if !in_quotes && (c == delimiter || c == '\n' || c == '\r') => {
+                if field_modified {
+                    fields_modified += 1;
+                    field_modified = false;
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    if field_modified {
+        fields_modified += 1;
+    }
+
+    ctx.processed_text = out;
+    fields_modified
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClampReport {
+    pub clamped: usize,
+    // (row, column, original value); row 0 is the header
+    pub out_of_range: Vec<(usize, usize, String)>,
+}
+
+// Integer literal with an optional sign; no dependency on big-integer types is
+// needed because values outside i64 only have to be recognized, not computed.
+fn is_integer_literal(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+// Clamp integer fields in `columns` to `[min, max]`, including values too large
+// for i64. Non-integer fields are left alone.
+pub fn clamp_integer_fields(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    columns: &[Column],
+    min: i64,
+    max: i64,
+) -> Result<ClampReport, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut report = ClampReport::default();
+    let Some(header) = rows.first() else {
+        return Ok(report);
+    };
+    let indices = columns
+        .iter()
+        .map(|c| c.resolve(header))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (row_index, row) in rows.iter_mut().enumerate().skip(1) {
+        for &index in &indices {
+            let Some(field) = row.get_mut(index) else {
+                continue;
+            };
+            let value = field.trim();
+            if !is_integer_literal(value) {
+                continue;
+            }
+            let clamped = match value.parse::<i64>() {
+                Ok(n) if n < min => min,
+                Ok(n) if n > max => max,
+                Ok(_) => continue,
+                // Parse only fails here on overflow, so the sign decides the side
+                Err(_) if value.starts_with('-') => min,
+                Err(_) => max,
+            };
+            report.out_of_range.push((row_index, index, field.clone()));
+            report.clamped += 1;
+            *field = clamped.to_string();
+        }
+    }
+
+    if report.clamped > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(report)
+}
+
+// Convert `1.234.567,89` to `1234567.89`. Matches digits (. digits)* , digits
+fn european_to_decimal(value: &str) -> Option<String> {
+    let (whole, fraction) = value.split_once(',')?;
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !all_digits(fraction) || !whole.split('.').all(all_digits) {
+        return None;
+    }
+    Some(format!("{}.{fraction}", whole.replace('.', "")))
+}
+
+// Rewrite European formatted numbers in `columns` using `.` as the decimal
+// separator and no thousands separator. Returns the number of fields repaired.
+pub fn repair_european_decimals(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    columns: &[Column],
+) -> Result<usize, Box<dyn Error>> {
+    let mut repaired = 0;
+    for column in columns {
+        repaired += map_column(ctx, delimiter, column, |value| {
+            european_to_decimal(value.trim())
+        })?;
+    }
+    Ok(repaired)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotingStrategy {
+    AlwaysQuote,
+    // Quote only fields holding the delimiter, a quote, or a line break
+    MinimalQuote,
+    // Never quote; fails if any field requires quoting
+    NeverQuote,
+}
+
+// Re-emit every field using a single quoting strategy
+pub fn standardize_quoting(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    strategy: QuotingStrategy,
+) -> Result<(), Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let quote_style = match strategy {
+        QuotingStrategy::AlwaysQuote => csv::QuoteStyle::Always,
+        QuotingStrategy::MinimalQuote => csv::QuoteStyle::Necessary,
+        QuotingStrategy::NeverQuote => {
+            for (row_index, row) in rows.iter().enumerate() {
+                if let Some(column) = row.iter().position(|v| needs_quotes(v, delimiter)) {
+                    return Err(format!(
+                        "field at row {row_index}, column {column} requires quoting"
+                    )
+                    .into());
+                }
+            }
+            csv::QuoteStyle::Never
+        }
+    };
+    ctx.processed_text = write_rows_with_quoting(&rows, delimiter, quote_style)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    pub bytes_in: usize,
+    pub bytes_out: usize,
+    // One human readable line per step that changed something
+    pub events: Vec<String>,
+    // Steps that could not run, with the reason
+    pub skipped: Vec<String>,
+}
+
+// Drop data rows made up entirely of `\0` fields. Returns the rows removed.
+pub fn remove_zero_byte_rows(ctx: &mut CsvContext, delimiter: u8) -> Result<usize, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let before = rows.len();
+    let mut index = 0;
+    rows.retain(|row| {
+        index += 1;
+        index == 1 || !is_zero_byte_row(row)
+    });
+    let removed = before - rows.len();
+    if removed > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(removed)
+}
+
+// Remove lines whose first non-whitespace text is `comment_prefix`. Lines are
+// split quote-aware, so text inside a multi-line quoted field is never removed.
+pub fn strip_comments(ctx: &mut CsvContext, comment_prefix: &str) -> usize {
+    if comment_prefix.is_empty() {
+        return 0;
+    }
+    let mut removed = 0;
+    let kept: String = split_records(&ctx.processed_text)
+        .into_iter()
+        .filter(|record| {
+            let is_comment = record.trim_start().starts_with(comment_prefix);
+            removed += usize::from(is_comment);
+            !is_comment
+        })
+        .collect();
+    ctx.processed_text = kept;
+    removed
+}
+
+// Remove the first `count` physical records (blank lines included), matching
+// the indices reported by `detect_data_start_row`. Returns the rows removed.
+pub fn skip_leading_rows(ctx: &mut CsvContext, count: usize) -> usize {
+    let records = split_records(&ctx.processed_text);
+    let removed = count.min(records.len());
+    ctx.processed_text = records[removed..].concat();
+    removed
+}
+
+// Remove every physical record from `from_row` (inclusive) to the end, as
+// reported by `detect_trailer_rows`. Returns the rows removed.
+pub fn strip_trailer_rows(ctx: &mut CsvContext, from_row: usize) -> usize {
+    let records = split_records(&ctx.processed_text);
+    let kept = from_row.min(records.len());
+    let removed = records.len() - kept;
+    ctx.processed_text = records[..kept].concat();
+    removed
+}
+
+// Turn fields made only of whitespace into empty strings so they compare
+// equal to real nulls. Returns the number of fields normalized.
+pub fn normalize_whitespace_fields(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+) -> Result<usize, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut normalized = 0;
+    for field in rows.iter_mut().flatten() {
+        if !field.is_empty() && field.chars().all(|c| c.is_whitespace()) {
+            field.clear();
+            normalized += 1;
+        }
+    }
+    if normalized > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(normalized)
+}
+
+// Replace each run of two or more spaces or tabs with a single space
+fn collapse_runs(value: &str) -> Option<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut run = 0;
+    let mut changed = false;
+    for c in value.chars() {
+        if c == ' ' || c == '\t' {
+            run += 1;
+            if run == 1 {
+                out.push(c);
+            } else {
+                out.pop();
+                out.push(' ');
+                changed = true;
+            }
+        } else {
+            run = 0;
+            out.push(c);
+        }
+    }
+    changed.then_some(out)
+}
+
+// Collapse runs of spaces and tabs inside data fields of `columns` to one
+// space. Line breaks in quoted fields are kept. Returns the fields modified.
+pub fn collapse_whitespace(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    columns: &ColumnSelection,
+) -> Result<usize, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(0);
+    };
+    let indices = columns.resolve(header)?;
+    let mut modified = 0;
+    for row in rows.iter_mut().skip(1) {
+        for &index in &indices {
+            let Some(field) = row.get_mut(index) else {
+                continue;
+            };
+            if let Some(collapsed) = collapse_runs(field) {
+                *field = collapsed;
+                modified += 1;
+            }
+        }
+    }
+    if modified > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(modified)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PadReport {
+    pub rows_padded: usize,
+    // Rows with more fields than the header; left untouched
+    pub long_rows: Vec<usize>,
+}
+
+// Append empty fields to data rows shorter than the header
+pub fn pad_rows(ctx: &mut CsvContext, delimiter: u8) -> Result<PadReport, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let mut report = PadReport::default();
+    let Some(expected) = rows.first().map(Vec::len) else {
+        return Ok(report);
+    };
+
+    for (row_index, row) in rows.iter_mut().enumerate().skip(1) {
+        if row.len() < expected {
+            row.resize(expected, String::new());
+            report.rows_padded += 1;
+        } else if row.len() > expected {
+            report.long_rows.push(row_index);
+        }
+    }
+    if report.rows_padded > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncationEvent {
+    pub row_index: usize,
+    pub original_count: usize,
+    pub expected_count: usize,
+    pub dropped_fields: Vec<String>,
+}
+
+// Drop fields past the header's column count. Returns the number of rows
+// truncated along with what was removed from each.
+pub fn truncate_long_rows(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+) -> Result<(usize, Vec<TruncationEvent>), Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(expected) = rows.first().map(Vec::len) else {
+        return Ok((0, Vec::new()));
+    };
+
+    let mut events = Vec::new();
+    for (row_index, row) in rows.iter_mut().enumerate().skip(1) {
+        if row.len() > expected {
+            events.push(TruncationEvent {
+                row_index,
+                original_count: row.len(),
+                expected_count: expected,
+                dropped_fields: row.split_off(expected),
+            });
+        }
+    }
+    if !events.is_empty() {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok((events.len(), events))
+}
+
+// Rejoin rows split by an unquoted newline inside a field. A short row is
+// merged with up to `max_join_attempts` following rows when that restores the
+// header's column count; the split field is rejoined with `\n`.
+pub fn merge_continuation_rows(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    max_join_attempts: usize,
+) -> Result<usize, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(expected) = rows.first().map(Vec::len) else {
+        return Ok(0);
+    };
+
+    let mut merged_rows = Vec::with_capacity(rows.len());
+    let mut merges = 0;
+    let mut i = 0;
+    while i < rows.len() {
+        let mut candidate = rows[i].clone();
+        let mut consumed = 0;
+        if i > 0 && candidate.len() < expected {
+            for next in rows.iter().skip(i + 1).take(max_join_attempts) {
+                let mut rest = next.iter();
+                if let (Some(last), Some(first)) = (candidate.last_mut(), rest.next()) {
+                    last.push('\n');
+                    last.push_str(first);
+                }
+                candidate.extend(rest.cloned());
+                consumed += 1;
+                if candidate.len() >= expected {
+                    break;
+                }
+            }
+        }
+
+        if consumed > 0 && candidate.len() == expected {
+            merged_rows.push(candidate);
+            merges += consumed;
+            i += consumed + 1;
+        } else {
+            merged_rows.push(rows[i].clone());
+            i += 1;
+        }
+    }
+
+    if merges > 0 {
+        ctx.processed_text = write_rows(&merged_rows, delimiter)?;
+    }
+    Ok(merges)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecialFloatReplacements {
+    pub nan: String,
+    pub inf: String,
+    pub neg_inf: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecialFloat {
+    Nan,
+    Inf,
+    NegInf,
+}
+
+// Python, R, and Excel spellings of NaN and infinity
+fn classify_special_float(value: &str) -> Option<SpecialFloat> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "nan" | "-nan" | "1.#ind" | "-1.#ind" | "1.#qnan" | "-1.#qnan" | "#num!" => {
+            Some(SpecialFloat::Nan)
+        }
+        "inf" | "+inf" | "infinity" | "+infinity" | "1.#inf" => Some(SpecialFloat::Inf),
+        "-inf" | "-infinity" | "-1.#inf" => Some(SpecialFloat::NegInf),
+        _ => None,
+    }
+}
+
+// Replace NaN and infinity spellings in `columns`. Returns the number of
+// fields replaced.
+pub fn normalize_special_floats(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    columns: &[Column],
+    replacements: &SpecialFloatReplacements,
+) -> Result<usize, Box<dyn Error>> {
+    let mut replaced = 0;
+    for column in columns {
+        replaced += map_column(ctx, delimiter, column, |value| {
+            let replacement = match classify_special_float(value)? {
+                SpecialFloat::Nan => &replacements.nan,
+                SpecialFloat::Inf => &replacements.inf,
+                SpecialFloat::NegInf => &replacements.neg_inf,
+            };
+            (replacement != value).then(|| replacement.clone())
+        })?;
+    }
+    Ok(replaced)
+}
+
+// Remove columns, header included, that are empty in every data row.
+// Returns the names of the removed columns.
+pub fn remove_empty_columns(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let empty: Vec<usize> = (0..header.len())
+        .filter(|&i| {
+            data.iter()
+                .all(|row| is_null_value(row.get(i).map_or("", |v| v), &[]))
+        })
+        .collect();
+    let removed: Vec<String> = empty.iter().map(|&i| header[i].clone()).collect();
+    if empty.is_empty() {
+        return Ok(removed);
+    }
+
+    for row in rows.iter_mut() {
+        for &index in empty.iter().rev() {
+            if index < row.len() {
+                row.remove(index);
+            }
+        }
+    }
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(removed)
+}
+
+// Remove rows identical to the header that appear again below it, as written
+// by exports that re-emit the header every N rows. Returns the rows removed.
+pub fn remove_repeated_headers(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+) -> Result<usize, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first().cloned() else {
+        return Ok(0);
+    };
+    let before = rows.len();
+    let mut index = 0;
+    rows.retain(|row| {
+        index += 1;
+        index == 1 || *row != header
+    });
+    let removed = before - rows.len();
+    if removed > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(removed)
+}
+
+// Drop `0x00` bytes from `raw_data`, and `\0` from `processed_text` so the
+// two stay in sync. Only meant for single-byte and UTF-8 input; UTF-16 text
+// is mostly null bytes. Returns the bytes removed from `raw_data`.
+pub fn remove_null_bytes(ctx: &mut CsvContext) -> usize {
+    let before = ctx.raw_data.len();
+    ctx.raw_data.retain(|&byte| byte != 0);
+    if ctx.processed_text.contains('\0') {
+        ctx.processed_text = ctx.processed_text.replace('\0', "");
+    }
+    before - ctx.raw_data.len()
+}
+
+// Default repair pipeline for comma delimited files
+pub fn repair(ctx: &mut CsvContext) -> RepairReport {
+    let (repaired, report) = CsvRepairBuilder::from_context(std::mem::take(ctx))
+        .remove_embedded_boms()
+        .normalize_crlf_in_quoted(b',', " ")
+        .build_context();
+    *ctx = repaired;
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn crlf_inside_quotes_is_replaced() {
+        let mut c = ctx("a,b\r\n\"one\r\ntwo\",\"x\ry\"\r\n");
+        let modified = normalize_crlf_in_quoted(&mut c, b',', " ");
+        assert_eq!(modified, 2);
+        assert_eq!(c.processed_text, "a,b\r\n\"one two\",\"x y\"\r\n");
+    }
+
+    #[test]
+    fn row_separators_are_untouched() {
+        let mut c = ctx("a,b\r\n1,2\r\n");
+        assert_eq!(normalize_crlf_in_quoted(&mut c, b',', " "), 0);
+        assert_eq!(c.processed_text, "a,b\r\n1,2\r\n");
+    }
+
+    #[test]
+    fn integers_are_clamped_including_overflow() {
+        let mut c = ctx("id,qty\n1,999999999999999999999999\n2,-5\n3,abc\n");
+        let report = clamp_integer_fields(&mut c, b',', &["qty".into()], 0, i64::MAX).unwrap();
+        assert_eq!(report.clamped, 2);
+        assert_eq!(report.out_of_range[1], (2, 1, "-5".to_string()));
+        assert_eq!(
+            c.processed_text,
+            format!("id,qty\n1,{}\n2,0\n3,abc\n", i64::MAX)
+        );
+
+        // Nothing out of range leaves the original quoting alone
+        let mut c = ctx("id,qty\n\"1\",5\n");
+        let report = clamp_integer_fields(&mut c, b',', &["qty".into()], 0, 10).unwrap();
+        assert_eq!(report.clamped, 0);
+        assert_eq!(c.processed_text, "id,qty\n\"1\",5\n");
+    }
+
+    #[test]
+    fn european_decimals_are_repaired() {
+        let mut c = ctx("amount\n\"1.234.567,89\"\n\"12,5\"\n1.234\nabc\n");
+        let repaired = repair_european_decimals(&mut c, b',', &[0.into()]).unwrap();
+        assert_eq!(repaired, 2);
+        assert_eq!(c.processed_text, "amount\n1234567.89\n12.5\n1.234\nabc\n");
+    }
+
+    #[test]
+    fn quoting_is_standardized() {
+        let mut c = ctx("\"a\",b\n\"1\",2\n");
+        standardize_quoting(&mut c, b',', QuotingStrategy::AlwaysQuote).unwrap();
+        assert_eq!(c.processed_text, "\"a\",\"b\"\n\"1\",\"2\"\n");
+        standardize_quoting(&mut c, b',', QuotingStrategy::NeverQuote).unwrap();
+        assert_eq!(c.processed_text, "a,b\n1,2\n");
+    }
+
+    #[test]
+    fn never_quote_rejects_fields_that_need_quotes() {
+        let mut c = ctx("a\n\"x,y\"\n");
+        assert!(standardize_quoting(&mut c, b',', QuotingStrategy::NeverQuote).is_err());
+    }
+
+    #[test]
+    fn zero_byte_rows_are_removed() {
+        let mut c = ctx("a,b\n\0,\0\n1,2\n");
+        assert_eq!(remove_zero_byte_rows(&mut c, b',').unwrap(), 1);
+        assert_eq!(c.processed_text, "a,b\n1,2\n");
+    }
+
+    #[test]
+    fn comment_lines_are_stripped() {
+        let mut c = ctx("# generated\na,b\n  // note\n\"x\n# not a comment\",1\n");
+        assert_eq!(strip_comments(&mut c, "#"), 1);
+        assert_eq!(
+            c.processed_text,
+            "a,b\n  // note\n\"x\n# not a comment\",1\n"
+        );
+        assert_eq!(strip_comments(&mut c, "//"), 1);
+    }
+
+    #[test]
+    fn leading_rows_are_skipped() {
+        let mut c = ctx("Title\n\nname,amount\nAda,10\n");
+        assert_eq!(skip_leading_rows(&mut c, 2), 2);
+        assert_eq!(c.processed_text, "name,amount\nAda,10\n");
+        assert_eq!(skip_leading_rows(&mut c, 10), 2);
+        assert_eq!(c.processed_text, "");
+    }
+
+    #[test]
+    fn trailer_rows_are_stripped() {
+        let mut c = ctx("item,q1\nnuts,1\n\nTotal,1\n");
+        assert_eq!(strip_trailer_rows(&mut c, 2), 2);
+        assert_eq!(c.processed_text, "item,q1\nnuts,1\n");
+    }
+
+    #[test]
+    fn whitespace_only_fields_become_empty() {
+        let mut c = ctx("a,b\n\"   \",x\n\t, y \n");
+        assert_eq!(normalize_whitespace_fields(&mut c, b',').unwrap(), 2);
+        assert_eq!(c.processed_text, "a,b\n,x\n, y \n");
+    }
+
+    #[test]
+    fn short_rows_are_padded_and_long_rows_flagged() {
+        let mut c = ctx("a,b,c\n1\n1,2,3,4\n");
+        let report = pad_rows(&mut c, b',').unwrap();
+        assert_eq!(report.rows_padded, 1);
+        assert_eq!(report.long_rows, vec![2]);
+        assert_eq!(c.processed_text, "a,b,c\n1,,\n1,2,3,4\n");
+    }
+
+    #[test]
+    fn long_rows_are_truncated() {
+        let mut c = ctx("a,b\n1,2,x,y\n3,4\n");
+        let (truncated, events) = truncate_long_rows(&mut c, b',').unwrap();
+        assert_eq!(truncated, 1);
+        assert_eq!(events[0].row_index, 1);
+        assert_eq!(
+            events[0].dropped_fields,
+            vec!["x".to_string(), "y".to_string()]
+        );
+        assert_eq!(c.processed_text, "a,b\n1,2\n3,4\n");
+    }
+
+    #[test]
+    fn continuation_rows_are_merged() {
+        let mut c = ctx("id,note,qty\n1,first line\nsecond line,5\n2,ok,3\n");
+        assert_eq!(merge_continuation_rows(&mut c, b',', 2).unwrap(), 1);
+        assert_eq!(
+            c.processed_text,
+            "id,note,qty\n1,\"first line\nsecond line\",5\n2,ok,3\n"
+        );
+    }
+
+    #[test]
+    fn unmergeable_short_rows_are_kept() {
+        let mut c = ctx("a,b,c\n1\n2,3,4,5\n");
+        assert_eq!(merge_continuation_rows(&mut c, b',', 1).unwrap(), 0);
+        assert_eq!(c.processed_text, "a,b,c\n1\n2,3,4,5\n");
+    }
+
+    #[test]
+    fn field_newlines_are_replaced() {
+        let mut c = ctx("a,b\n\"x\ny\",\"p\r\nq\"\n");
+        assert_eq!(strip_field_newlines(&mut c, b',', "\\n"), 2);
+        assert_eq!(c.processed_text, "a,b\n\"x\\ny\",\"p\\nq\"\n");
+    }
+
+    #[test]
+    fn special_floats_are_replaced() {
+        let mut c = ctx("x\nNaN\n1.#INF\n-Inf\n1.#IND\n2.5\n");
+        let replacements = SpecialFloatReplacements {
+            nan: String::new(),
+            inf: "1e308".to_string(),
+            neg_inf: "-1e308".to_string(),
+        };
+        let replaced = normalize_special_floats(&mut c, b',', &[0.into()], &replacements).unwrap();
+        assert_eq!(replaced, 4);
+        assert_eq!(c.processed_text, "x\n\"\"\n1e308\n-1e308\n\"\"\n2.5\n");
+    }
+
+    #[test]
+    fn empty_columns_are_removed() {
+        let mut c = ctx("a,b,c,\n1,,x,\n2, ,y,\n");
+        let removed = remove_empty_columns(&mut c, b',').unwrap();
+        assert_eq!(removed, vec!["b".to_string(), String::new()]);
+        assert_eq!(c.processed_text, "a,c\n1,x\n2,y\n");
+    }
+
+    #[test]
+    fn repeated_headers_are_removed() {
+        let mut c = ctx("a,b\n1,2\na,b\n3,4\n\"a\",b\n");
+        assert_eq!(remove_repeated_headers(&mut c, b',').unwrap(), 2);
+        assert_eq!(c.processed_text, "a,b\n1,2\n3,4\n");
+    }
+
+    #[test]
+    fn null_bytes_are_removed_from_raw_data() {
+        let mut c = CsvContext::new(b"a,b\n1\x00,\x002\n".to_vec());
+        assert_eq!(remove_null_bytes(&mut c), 2);
+        assert_eq!(c.raw_data, b"a,b\n1,2\n");
+        assert_eq!(c.processed_text, "a,b\n1,2\n");
+    }
+
+    #[test]
+    fn whitespace_runs_are_collapsed() {
+        let mut c = ctx("name,city\nJohn  Smith,New \t York   City\n\"a \n  b\",x\ty\n");
+        let columns = ColumnSelection::All;
+        assert_eq!(collapse_whitespace(&mut c, b',', &columns).unwrap(), 3);
+        assert_eq!(
+            c.processed_text,
+            "name,city\nJohn Smith,New York City\n\"a \n b\",x\ty\n"
+        );
+    }
+}
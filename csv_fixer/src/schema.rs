@@ -0,0 +1,469 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::context::CsvContext;
+use crate::csv_io::read_rows;
+
+// Maximum number of example values kept per column
+const MAX_EXAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Number,
+    Boolean,
+    String,
+}
+
+impl ColumnType {
+    // Name used by JSON Schema
+    pub fn json_name(self) -> &'static str {
+        match self {
+            ColumnType::Integer => "integer",
+            ColumnType::Number => "number",
+            ColumnType::Boolean => "boolean",
+            ColumnType::String => "string",
+        }
+    }
+
+    pub fn matches(self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            ColumnType::Integer => value.parse::<i64>().is_ok(),
+            ColumnType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            ColumnType::Boolean => {
+                value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+            }
+            ColumnType::String => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub nullable: bool,
+    pub examples: Vec<String>,
+}
+
+// Infer a type for each column from the first `sample_rows` data rows. The
+// narrowest type matching every non-empty value wins; columns without values
+// default to string.
+pub fn infer_schema(
+    ctx: &CsvContext,
+    delimiter: u8,
+    sample_rows: usize,
+) -> Result<Vec<ColumnSchema>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let sample = &data[..data.len().min(sample_rows)];
+
+    let mut schema = Vec::with_capacity(header.len());
+    for (index, name) in header.iter().enumerate() {
+        let values: Vec<&str> = sample
+            .iter()
+            .map(|row| row.get(index).map_or("", |v| v.trim()))
+            .collect();
+        let present: Vec<&str> = values.iter().copied().filter(|v| !v.is_empty()).collect();
+        let column_type = if present.is_empty() {
+            ColumnType::String
+        } else {
+            [ColumnType::Integer, ColumnType::Number, ColumnType::Boolean]
+                .into_iter()
+                .find(|t| present.iter().all(|v| t.matches(v)))
+                .unwrap_or(ColumnType::String)
+        };
+
+        let mut examples: Vec<String> = Vec::new();
+        for value in &present {
+            if examples.len() == MAX_EXAMPLES {
+                break;
+            }
+            if !examples.iter().any(|e| e == value) {
+                examples.push(value.to_string());
+            }
+        }
+
+        schema.push(ColumnSchema {
+            name: name.clone(),
+            column_type,
+            nullable: present.len() < values.len(),
+            examples,
+        });
+    }
+    Ok(schema)
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Numbers are re-serialized so forms like `+5`, `007`, or `.5` come out as
+// valid JSON
+fn json_example(value: &str, column_type: ColumnType) -> String {
+    match column_type {
+        ColumnType::Integer => match value.trim().parse::<i64>() {
+            Ok(n) => n.to_string(),
+            Err(_) => json_string(value),
+        },
+        ColumnType::Number => match value.trim().parse::<f64>() {
+            Ok(n) if n.is_finite() => n.to_string(),
+            _ => json_string(value),
+        },
+        ColumnType::Boolean => value.to_ascii_lowercase(),
+        ColumnType::String => json_string(value),
+    }
+}
+
+// Describe the CSV rows as a JSON Schema object, with example values taken
+// from the data. Columns without empty values are listed as required.
+pub fn generate_schema_json(
+    ctx: &CsvContext,
+    delimiter: u8,
+    sample_rows: usize,
+) -> Result<String, Box<dyn Error>> {
+    let schema = infer_schema(ctx, delimiter, sample_rows)?;
+
+    let properties: Vec<String> = schema
+        .iter()
+        .map(|column| {
+            let examples: Vec<String> = column
+                .examples
+                .iter()
+                .map(|e| json_example(e, column.column_type))
+                .collect();
+            format!(
+                "    {}: {{\"type\": \"{}\", \"examples\": [{}]}}",
+                json_string(&column.name),
+                column.column_type.json_name(),
+                examples.join(", ")
+            )
+        })
+        .collect();
+    let required: Vec<String> = schema
+        .iter()
+        .filter(|column| !column.nullable)
+        .map(|column| json_string(&column.name))
+        .collect();
+
+    Ok(format!(
+        "{{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \"type\": \"object\",\n  \"properties\": {{\n{}\n  }},\n  \"required\": [{}]\n}}\n",
+        properties.join(",\n"),
+        required.join(", ")
+    ))
+}
+
+// Just enough JSON to read back schemas written by `generate_schema_json`
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.input[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Box<dyn Error>> {
+        if self.peek() != Some(c) {
+            return Err(format!("expected '{c}' at byte {}", self.pos).into());
+        }
+        self.pos += c.len_utf8();
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json, Box<dyn Error>> {
+        match self.peek().ok_or("unexpected end of JSON")? {
+            '{' => {
+                self.expect('{')?;
+                let mut entries = Vec::new();
+                while self.peek() != Some('}') {
+                    if !entries.is_empty() {
+                        self.expect(',')?;
+                    }
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.value()?));
+                }
+                self.expect('}')?;
+                Ok(Json::Object(entries))
+            }
+            '[' => {
+                self.expect('[')?;
+                let mut items = Vec::new();
+                while self.peek() != Some(']') {
+                    if !items.is_empty() {
+                        self.expect(',')?;
+                    }
+                    items.push(self.value()?);
+                }
+                self.expect(']')?;
+                Ok(Json::Array(items))
+            }
+            '"' => Ok(Json::String(self.string()?)),
+            _ => {
+                let rest = &self.input[self.pos..];
+                let len = rest
+                    .find(|c: char| matches!(c, ',' | '}' | ']') || c.is_whitespace())
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                match &rest[..len] {
+                    "null" => Ok(Json::Null),
+                    "true" => Ok(Json::Bool(true)),
+                    "false" => Ok(Json::Bool(false)),
+                    number => Ok(Json::Number(
+                        number
+                            .parse()
+                            .map_err(|_| format!("invalid JSON value: {number}"))?,
+                    )),
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Box<dyn Error>> {
+        self.expect('"')?;
+        let mut out = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let (_, escaped) = chars.next().ok_or("unterminated JSON string")?;
+                    out.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or(format!("invalid JSON escape: \\u{hex}"))?
+                        }
+                        other => other,
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+        Err("unterminated JSON string".into())
+    }
+}
+
+fn parse_json(input: &str) -> Result<Json, Box<dyn Error>> {
+    let mut parser = JsonParser { input, pos: 0 };
+    let value = parser.value()?;
+    if parser.peek().is_some() {
+        return Err(format!("trailing data after JSON at byte {}", parser.pos).into());
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    // Row 0 is the header, used for missing columns
+    pub row: usize,
+    pub column: String,
+    pub reason: String,
+}
+
+// Validate the CSV against a JSON Schema file in the format written by
+// `generate_schema_json`. Required columns must be present with no empty
+// values, and non-empty values must match the property's type. Columns not
+// in the schema are allowed. An empty result means the file is valid.
+pub fn validate_with_schema_file<P: AsRef<Path>>(
+    ctx: &CsvContext,
+    delimiter: u8,
+    schema_path: P,
+) -> Result<Vec<SchemaViolation>, Box<dyn Error>> {
+    let schema = parse_json(&fs::read_to_string(schema_path)?)?;
+    let Some(Json::Object(properties)) = schema.get("properties") else {
+        return Err("schema has no \"properties\" object".into());
+    };
+    let required: Vec<&str> = match schema.get("required") {
+        Some(Json::Array(names)) => names
+            .iter()
+            .filter_map(|name| match name {
+                Json::String(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let empty = Vec::new();
+    let header = rows.first().unwrap_or(&empty);
+    let mut violations = Vec::new();
+    for (name, property) in properties {
+        let is_required = required.contains(&name.as_str());
+        let Some(index) = header.iter().position(|h| h == name) else {
+            if is_required {
+                violations.push(SchemaViolation {
+                    row: 0,
+                    column: name.clone(),
+                    reason: "required column is missing".to_string(),
+                });
+            }
+            continue;
+        };
+        let column_type = match property.get("type") {
+            Some(Json::String(t)) => [
+                ColumnType::Integer,
+                ColumnType::Number,
+                ColumnType::Boolean,
+                ColumnType::String,
+            ]
+            .into_iter()
+            .find(|candidate| candidate.json_name() == t)
+            .ok_or(format!("unsupported type for {name}: {t}"))?,
+            _ => ColumnType::String,
+        };
+
+        for (row_index, row) in rows.iter().enumerate().skip(1) {
+            let value = row.get(index).map_or("", |v| v.trim());
+            let reason = if value.is_empty() {
+                is_required.then(|| "required value is empty".to_string())
+            } else {
+                (!column_type.matches(value))
+                    .then(|| format!("expected {}, got {value:?}", column_type.json_name()))
+            };
+            if let Some(reason) = reason {
+                violations.push(SchemaViolation {
+                    row: row_index,
+                    column: name.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn schema_types_are_inferred() {
+        let c = ctx("id,price,active,name\n1,9.5,true,Ada\n2,10,FALSE,\n");
+        let schema = infer_schema(&c, b',', 100).unwrap();
+        let types: Vec<ColumnType> = schema.iter().map(|c| c.column_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                ColumnType::Integer,
+                ColumnType::Number,
+                ColumnType::Boolean,
+                ColumnType::String,
+            ]
+        );
+        assert!(schema[3].nullable && !schema[0].nullable);
+    }
+
+    #[test]
+    fn schema_json_lists_properties_and_required() {
+        let c = ctx("id,name\n1,\"Ada \"\"A\"\"\"\n2,\n");
+        let json = generate_schema_json(&c, b',', 10).unwrap();
+        assert!(json.contains("\"id\": {\"type\": \"integer\", \"examples\": [1, 2]}"));
+        assert!(
+            json.contains("\"name\": {\"type\": \"string\", \"examples\": [\"Ada \\\"A\\\"\"]}")
+        );
+        assert!(json.contains("\"required\": [\"id\"]"));
+    }
+
+    #[test]
+    fn schema_json_examples_are_valid_numbers() {
+        let c = ctx("n,x\n+5,.5\n007,5.\n-0,1e5\n");
+        let json = generate_schema_json(&c, b',', 10).unwrap();
+        assert!(json.contains("\"n\": {\"type\": \"integer\", \"examples\": [5, 7, 0]}"));
+        assert!(json.contains("\"x\": {\"type\": \"number\", \"examples\": [0.5, 5, 100000]}"));
+    }
+
+    #[test]
+    fn generated_schema_file_validates_data() {
+        let sample = ctx("id,name,score\n1,Ada,9.5\n2,Bob,7\n");
+        let path = std::env::temp_dir().join(format!(
+            "csv_fixer_schema_file_test_{}.schema.json",
+            std::process::id()
+        ));
+        fs::write(&path, generate_schema_json(&sample, b',', 10).unwrap()).unwrap();
+
+        assert!(
+            validate_with_schema_file(&sample, b',', &path)
+                .unwrap()
+                .is_empty()
+        );
+
+        let bad = ctx("id,score\nx,\"1\\u\"\n,high\n");
+        let violations = validate_with_schema_file(&bad, b',', &path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let found: Vec<(usize, &str)> = violations
+            .iter()
+            .map(|v| (v.row, v.column.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "id"),
+                (2, "id"),
+                (0, "name"),
+                (1, "score"),
+                (2, "score")
+            ]
+        );
+    }
+}
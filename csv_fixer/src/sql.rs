@@ -0,0 +1,192 @@
+use std::error::Error;
+
+use crate::context::CsvContext;
+use crate::csv_io::read_rows;
+use crate::schema::{ColumnType, infer_schema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    fn quote_identifier(self, name: &str) -> String {
+        match self {
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            }
+        }
+    }
+
+    fn column_type(self, column_type: ColumnType) -> &'static str {
+        match (self, column_type) {
+            (SqlDialect::Postgres, ColumnType::Integer) => "BIGINT",
+            (SqlDialect::Postgres, ColumnType::Number) => "DOUBLE PRECISION",
+            (SqlDialect::Postgres, ColumnType::Boolean) => "BOOLEAN",
+            (SqlDialect::Postgres, ColumnType::String) => "TEXT",
+            (SqlDialect::MySql, ColumnType::Integer) => "BIGINT",
+            (SqlDialect::MySql, ColumnType::Number) => "DOUBLE",
+            (SqlDialect::MySql, ColumnType::Boolean) => "BOOLEAN",
+            (SqlDialect::MySql, ColumnType::String) => "VARCHAR(255)",
+            (SqlDialect::Sqlite, ColumnType::Integer | ColumnType::Boolean) => "INTEGER",
+            (SqlDialect::Sqlite, ColumnType::Number) => "REAL",
+            (SqlDialect::Sqlite, ColumnType::String) => "TEXT",
+        }
+    }
+
+    fn string_literal(self, value: &str) -> String {
+        let escaped = value.replace('\'', "''");
+        match self {
+            // MySQL treats backslash as an escape character by default
+            SqlDialect::MySql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            SqlDialect::Postgres | SqlDialect::Sqlite => format!("'{escaped}'"),
+        }
+    }
+
+    fn literal(self, value: &str, column_type: ColumnType) -> String {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return "NULL".to_string();
+        }
+        if !column_type.matches(trimmed) {
+            return self.string_literal(value);
+        }
+        match (self, column_type) {
+            (_, ColumnType::Integer | ColumnType::Number) => trimmed.to_string(),
+            (SqlDialect::Sqlite, ColumnType::Boolean) => if trimmed.eq_ignore_ascii_case("true") {
+                "1"
+            } else {
+                "0"
+            }
+            .to_string(),
+            (_, ColumnType::Boolean) => trimmed.to_ascii_uppercase(),
+            (_, ColumnType::String) => self.string_literal(value),
+        }
+    }
+}
+
+// `id`, `pk`, and `*_id` names usually hold keys
+fn looks_like_key(name: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    name == "id" || name == "pk" || name.ends_with("_id")
+}
+
+// Build a CREATE TABLE statement from the inferred schema. Key-like columns
+// are flagged with a comment rather than a constraint, since the name alone
+// does not prove uniqueness.
+pub fn generate_create_table(
+    ctx: &CsvContext,
+    delimiter: u8,
+    table_name: &str,
+    dialect: SqlDialect,
+    sample_rows: usize,
+) -> Result<String, Box<dyn Error>> {
+    let schema = infer_schema(ctx, delimiter, sample_rows)?;
+    if schema.is_empty() {
+        return Err("cannot create a table without a header row".into());
+    }
+
+    let mut lines = Vec::with_capacity(schema.len());
+    for (index, column) in schema.iter().enumerate() {
+        let mut line = format!(
+            "  {} {}",
+            dialect.quote_identifier(&column.name),
+            dialect.column_type(column.column_type)
+        );
+        if !column.nullable {
+            line.push_str(" NOT NULL");
+        }
+        if index + 1 < schema.len() {
+            line.push(',');
+        }
+        if looks_like_key(&column.name) {
+            line.push_str(" -- likely primary key");
+        }
+        lines.push(line);
+    }
+
+    Ok(format!(
+        "CREATE TABLE {} (\n{}\n);\n",
+        dialect.quote_identifier(table_name),
+        lines.join("\n")
+    ))
+}
+
+// Emit multi-row INSERT statements with `batch_size` rows each. Column types
+// come from the whole file so literals are only left unquoted when every value
+// in the column fits the type. Empty fields become NULL.
+pub fn generate_inserts(
+    ctx: &CsvContext,
+    delimiter: u8,
+    table_name: &str,
+    dialect: SqlDialect,
+    batch_size: usize,
+) -> Result<String, Box<dyn Error>> {
+    let schema = infer_schema(ctx, delimiter, usize::MAX)?;
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let columns: Vec<String> = schema
+        .iter()
+        .map(|column| dialect.quote_identifier(&column.name))
+        .collect();
+    let prefix = format!(
+        "INSERT INTO {} ({}) VALUES\n",
+        dialect.quote_identifier(table_name),
+        columns.join(", ")
+    );
+
+    let mut out = String::new();
+    for batch in rows.get(1..).unwrap_or_default().chunks(batch_size.max(1)) {
+        let values: Vec<String> = batch
+            .iter()
+            .map(|row| {
+                let literals: Vec<String> = schema
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        dialect.literal(row.get(i).map_or("", |v| v), column.column_type)
+                    })
+                    .collect();
+                format!("  ({})", literals.join(", "))
+            })
+            .collect();
+        out.push_str(&prefix);
+        out.push_str(&values.join(",\n"));
+        out.push_str(";\n");
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn create_table_maps_types_per_dialect() {
+        let c = ctx("id,price,name\n1,9.5,Ada\n2,3,\n");
+        let mysql = generate_create_table(&c, b',', "items", SqlDialect::MySql, 10).unwrap();
+        assert_eq!(
+            mysql,
+            "CREATE TABLE `items` (\n  `id` BIGINT NOT NULL, -- likely primary key\n  `price` DOUBLE NOT NULL,\n  `name` VARCHAR(255)\n);\n"
+        );
+        let sqlite = generate_create_table(&c, b',', "items", SqlDialect::Sqlite, 10).unwrap();
+        assert!(sqlite.contains("\"name\" TEXT\n"));
+    }
+
+    #[test]
+    fn inserts_are_batched_and_escaped() {
+        let c = ctx("id,name,active\n1,O'Brien,true\n2,,false\n3,Ada,true\n");
+        let sql = generate_inserts(&c, b',', "people", SqlDialect::Sqlite, 2).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO \"people\" (\"id\", \"name\", \"active\") VALUES\n  (1, 'O''Brien', 1),\n  (2, NULL, 0);\n\
+             INSERT INTO \"people\" (\"id\", \"name\", \"active\") VALUES\n  (3, 'Ada', 1);\n"
+        );
+    }
+}
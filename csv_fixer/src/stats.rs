@@ -0,0 +1,379 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use crate::column::{Column, ColumnSelection};
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+use crate::schema::{ColumnType, infer_schema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Count,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn name(self) -> &'static str {
+        match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Count => "count",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    pub csv: String,
+    // Values that are not finite numbers, NaN and infinities included
+    // (SUM/MIN/MAX only)
+    pub skipped: usize,
+}
+
+// Group data rows by `group_col` and aggregate `value_col`. The result is a
+// new CSV; the context is left untouched. Groups keep first-seen order.
+pub fn aggregate(
+    ctx: &CsvContext,
+    delimiter: u8,
+    group_col: &Column,
+    value_col: &Column,
+    operation: Aggregate,
+) -> Result<Aggregation, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(Aggregation {
+            csv: String::new(),
+            skipped: 0,
+        });
+    };
+    let group = group_col.resolve(header)?;
+    let value = value_col.resolve(header)?;
+
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Option<f64>> = HashMap::new();
+    let mut skipped = 0;
+
+    for row in rows.iter().skip(1) {
+        let key = row.get(group).cloned().unwrap_or_default();
+        let entry = groups.entry(key.clone()).or_insert_with(|| {
+            order.push(key);
+            None
+        });
+
+        if operation == Aggregate::Count {
+            *entry = Some(entry.unwrap_or(0.0) + 1.0);
+            continue;
+        }
+        let parsed = row
+            .get(value)
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|n| n.is_finite());
+        let Some(number) = parsed else {
+            skipped += 1;
+            continue;
+        };
+        *entry = Some(match (*entry, operation) {
+            (None, _) => number,
+            (Some(acc), Aggregate::Sum) => acc + number,
+            (Some(acc), Aggregate::Min) => acc.min(number),
+            (Some(acc), Aggregate::Max) => acc.max(number),
+            (Some(acc), Aggregate::Count) => acc,
+        });
+    }
+
+    let mut out = vec![vec![
+        header[group].clone(),
+        format!("{}_{}", operation.name(), header[value]),
+    ]];
+    for key in order {
+        let result = groups[&key].map(|v| v.to_string()).unwrap_or_default();
+        out.push(vec![key, result]);
+    }
+
+    Ok(Aggregation {
+        csv: write_rows(&out, delimiter)?,
+        skipped,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cardinality {
+    pub column: String,
+    pub unique_count: usize,
+    pub total_count: usize,
+    // Near 1.0 suggests an identifier, near 0.0 a categorical
+    pub ratio: f64,
+}
+
+// Count distinct values per column over the data rows
+pub fn column_cardinality(
+    ctx: &CsvContext,
+    delimiter: u8,
+    columns: &ColumnSelection,
+) -> Result<Vec<Cardinality>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut result = Vec::new();
+    for index in columns.resolve(header)? {
+        let unique: HashSet<&str> = data
+            .iter()
+            .map(|row| row.get(index).map_or("", String::as_str))
+            .collect();
+        let total_count = data.len();
+        result.push(Cardinality {
+            column: header[index].clone(),
+            unique_count: unique.len(),
+            total_count,
+            ratio: if total_count == 0 {
+                0.0
+            } else {
+                unique.len() as f64 / total_count as f64
+            },
+        });
+    }
+    Ok(result)
+}
+
+// Columns inferred as strings whose unique ratio is above `threshold`, as
+// (column name, ratio). These are likely identifiers such as ids or UUIDs.
+pub fn find_high_cardinality_columns(
+    ctx: &CsvContext,
+    delimiter: u8,
+    threshold: f64,
+) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
+    let string_columns: Vec<Column> = infer_schema(ctx, delimiter, usize::MAX)?
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.column_type == ColumnType::String)
+        .map(|(index, _)| Column::Index(index))
+        .collect();
+    if string_columns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let selection = ColumnSelection::Only(string_columns);
+    Ok(column_cardinality(ctx, delimiter, &selection)?
+        .into_iter()
+        .filter(|cardinality| cardinality.ratio > threshold)
+        .map(|cardinality| (cardinality.column, cardinality.ratio))
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Correlation {
+    // NaN when fewer than two pairs or either column is constant
+    pub r: f64,
+    pub n_pairs: usize,
+    pub n_skipped: usize,
+}
+
+// Pearson correlation between two numeric columns in a single pass, using
+// Welford-style running means and co-moments. Rows where either value is not
+// a finite number (NaN and infinities included) are skipped.
+pub fn column_correlation(
+    ctx: &CsvContext,
+    delimiter: u8,
+    col_a: &Column,
+    col_b: &Column,
+) -> Result<Correlation, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(Correlation {
+            r: f64::NAN,
+            n_pairs: 0,
+            n_skipped: 0,
+        });
+    };
+    let a = col_a.resolve(header)?;
+    let b = col_b.resolve(header)?;
+
+    let (mut n, mut skipped) = (0usize, 0usize);
+    let (mut mean_x, mut mean_y) = (0.0, 0.0);
+    let (mut m2_x, mut m2_y, mut co_moment) = (0.0, 0.0, 0.0);
+    for row in rows.iter().skip(1) {
+        let parse = |i: usize| {
+            row.get(i)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|n| n.is_finite())
+        };
+        let (Some(x), Some(y)) = (parse(a), parse(b)) else {
+            skipped += 1;
+            continue;
+        };
+        n += 1;
+        let dx = x - mean_x;
+        mean_x += dx / n as f64;
+        let dy = y - mean_y;
+        mean_y += dy / n as f64;
+        m2_x += dx * (x - mean_x);
+        m2_y += dy * (y - mean_y);
+        co_moment += dx * (y - mean_y);
+    }
+
+    Ok(Correlation {
+        r: co_moment / (m2_x * m2_y).sqrt(),
+        n_pairs: n,
+        n_skipped: skipped,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outlier {
+    pub row: usize,
+    pub column: String,
+    pub value: f64,
+    // The lower or upper fence the value fell outside of
+    pub bound_exceeded: f64,
+}
+
+// Quantile of sorted `values` by linear interpolation between closest ranks
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+// Flag values outside `[Q1 - k * IQR, Q3 + k * IQR]` for each column, with
+// `k` being `iqr_multiplier`. Non-numeric and non-finite values are ignored.
+pub fn detect_outliers_iqr(
+    ctx: &CsvContext,
+    delimiter: u8,
+    columns: &[Column],
+    iqr_multiplier: f64,
+) -> Result<Vec<Outlier>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut outliers = Vec::new();
+    for column in columns {
+        let index = column.resolve(header)?;
+        let values: Vec<(usize, f64)> = rows
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter_map(|(row, fields)| {
+                let value = fields.get(index)?.trim().parse::<f64>().ok()?;
+                value.is_finite().then_some((row, value))
+            })
+            .collect();
+        if values.is_empty() {
+            continue;
+        }
+
+        let mut sorted: Vec<f64> = values.iter().map(|&(_, value)| value).collect();
+        sorted.sort_by(f64::total_cmp);
+        let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+        let spread = iqr_multiplier * (q3 - q1);
+        let (low, high) = (q1 - spread, q3 + spread);
+
+        for (row, value) in values {
+            let bound_exceeded = if value < low {
+                low
+            } else if value > high {
+                high
+            } else {
+                continue;
+            };
+            outliers.push(Outlier {
+                row,
+                column: header[index].clone(),
+                value,
+                bound_exceeded,
+            });
+        }
+    }
+    Ok(outliers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn aggregate_sums_and_skips_non_numeric() {
+        let c = ctx("region,amount\neast,10\nwest,2.5\neast,n/a\neast,5\nwest,NaN\neast,-inf\n");
+        let result = aggregate(&c, b',', &0.into(), &"amount".into(), Aggregate::Sum).unwrap();
+        assert_eq!(result.csv, "region,sum_amount\neast,15\nwest,2.5\n");
+        assert_eq!(result.skipped, 3);
+    }
+
+    #[test]
+    fn aggregate_counts_all_rows() {
+        let c = ctx("region,amount\neast,10\neast,x\n");
+        let result = aggregate(&c, b',', &0.into(), &1.into(), Aggregate::Count).unwrap();
+        assert_eq!(result.csv, "region,count_amount\neast,2\n");
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[test]
+    fn cardinality_counts_unique_values() {
+        let c = ctx("id,color\n1,red\n2,red\n3,blue\n4,red\n");
+        let result = column_cardinality(&c, b',', &ColumnSelection::All).unwrap();
+        assert_eq!(result[0].unique_count, 4);
+        assert_eq!(result[0].ratio, 1.0);
+        assert_eq!(result[1].unique_count, 2);
+        assert_eq!(result[1].ratio, 0.5);
+
+        let only = ColumnSelection::Only(vec!["color".into()]);
+        assert_eq!(column_cardinality(&c, b',', &only).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn correlation_is_computed_in_one_pass() {
+        let c = ctx("x,y\n1,2\n2,4\n3,6\nn/a,1\n4,8\nNaN,3\n5,inf\n");
+        let result = column_correlation(&c, b',', &"x".into(), &"y".into()).unwrap();
+        assert!((result.r - 1.0).abs() < 1e-12);
+        assert_eq!(result.n_pairs, 4);
+        assert_eq!(result.n_skipped, 3);
+
+        let inverse = ctx("x,y\n1,3\n2,2\n3,1\n");
+        let result = column_correlation(&inverse, b',', &0.into(), &1.into()).unwrap();
+        assert!((result.r + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn iqr_outliers_are_flagged() {
+        let c = ctx("id,v\n1,1\n2,2\n3,3\n4,4\n5,100\n6,x\n7,-50\n");
+        let outliers = detect_outliers_iqr(&c, b',', &["v".into()], 1.5).unwrap();
+        // Sorted: -50 1 2 3 4 100 -> Q1 1.25, Q3 3.75, fences -2.5 and 7.5
+        assert_eq!(
+            outliers,
+            vec![
+                Outlier {
+                    row: 5,
+                    column: "v".to_string(),
+                    value: 100.0,
+                    bound_exceeded: 7.5,
+                },
+                Outlier {
+                    row: 7,
+                    column: "v".to_string(),
+                    value: -50.0,
+                    bound_exceeded: -2.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn high_cardinality_string_columns_are_found() {
+        let c = ctx("id,code,color,n\na1,x,red,1\nb2,y,red,2\nc3,z,blue,3\nd4,x,red,4\n");
+        let found = find_high_cardinality_columns(&c, b',', 0.7).unwrap();
+        assert_eq!(
+            found,
+            vec![("id".to_string(), 1.0), ("code".to_string(), 0.75)]
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutError {
+    TimedOut { timeout_ms: u64 },
+    // The worker panicked; holds the panic message when it was a string
+    WorkerPanicked(String),
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutError::TimedOut { timeout_ms } => {
+                write!(f, "operation timed out after {timeout_ms} ms")
+            }
+            TimeoutError::WorkerPanicked(message) => write!(f, "worker panicked: {message}"),
+        }
+    }
+}
+
+impl Error for TimeoutError {}
+
+// Run `f` on a worker thread and wait at most `timeout_ms` for its result.
+// Threads cannot be killed, so a worker that overruns is detached and its
+// result is discarded when it eventually finishes. A panic in `f` is
+// reported as soon as it happens rather than as a timeout.
+pub fn with_timeout<F, R>(f: F, timeout_ms: u64) -> Result<R, TimeoutError>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default()
+        });
+        // The receiver is gone if we already timed out
+        let _ = tx.send(result);
+    });
+
+    match rx.recv_timeout(Duration::from_millis(timeout_ms)) {
+        Ok(result) => result.map_err(TimeoutError::WorkerPanicked),
+        Err(RecvTimeoutError::Timeout) => Err(TimeoutError::TimedOut { timeout_ms }),
+        Err(RecvTimeoutError::Disconnected) => Err(TimeoutError::WorkerPanicked(String::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_work_returns_result() {
+        assert_eq!(with_timeout(|| 2 + 2, 1000), Ok(4));
+    }
+
+    #[test]
+    fn slow_work_times_out() {
+        let result = with_timeout(|| thread::sleep(Duration::from_millis(500)), 10);
+        assert_eq!(result, Err(TimeoutError::TimedOut { timeout_ms: 10 }));
+    }
+
+    #[test]
+    fn panics_are_not_timeouts() {
+        let result: Result<(), _> = with_timeout(|| panic!("bad row"), 10_000);
+        assert_eq!(
+            result,
+            Err(TimeoutError::WorkerPanicked("bad row".to_string()))
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, write_rows};
+use crate::validate::{column_values, is_null_value};
+
+#[derive(Debug, Clone)]
+pub struct ConcatenateOptions {
+    pub separator: String,
+    pub drop_originals: bool,
+    // Position of the new column; appended as the last column when None
+    pub insert_at: Option<usize>,
+}
+
+impl Default for ConcatenateOptions {
+    fn default() -> Self {
+        Self {
+            separator: " ".to_string(),
+            drop_originals: false,
+            insert_at: None,
+        }
+    }
+}
+
+// Merge two columns into a new one holding `a + separator + b`
+pub fn concatenate_columns(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    col_a: &Column,
+    col_b: &Column,
+    new_name: &str,
+    options: &ConcatenateOptions,
+) -> Result<(), Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(());
+    };
+    let a = col_a.resolve(header)?;
+    let b = col_b.resolve(header)?;
+    let mut dropped = vec![a, b];
+    dropped.sort_unstable();
+    dropped.dedup();
+    // Default position is the end of the header, even on ragged rows
+    let width = header.len();
+    let end = if options.drop_originals {
+        width - dropped.len()
+    } else {
+        width
+    };
+
+    for (row_index, row) in rows.iter_mut().enumerate() {
+        if row.len() < width {
+            row.resize(width, String::new());
+        }
+        let value = if row_index == 0 {
+            new_name.to_string()
+        } else {
+            let field_a = row.get(a).map(String::as_str).unwrap_or("");
+            let field_b = row.get(b).map(String::as_str).unwrap_or("");
+            format!("{field_a}{}{field_b}", options.separator)
+        };
+
+        if options.drop_originals {
+            // Remove from the right so the remaining indices stay valid
+            for &index in dropped.iter().rev() {
+                if index < row.len() {
+                    row.remove(index);
+                }
+            }
+        }
+
+        let position = options.insert_at.unwrap_or(end).min(row.len());
+        row.insert(position, value);
+    }
+
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(())
+}
+
+// Split one column into several by `sub_delimiter`, replacing the original.
+// At most `max_splits` splits are made; parts are padded with empty fields or
+// truncated to match `new_names`. Returns the number of data rows split.
+pub fn split_column(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    sub_delimiter: &str,
+    new_names: &[String],
+    max_splits: usize,
+) -> Result<usize, Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(0);
+    };
+    let index = column.resolve(header)?;
+    let mut rows_affected = 0;
+
+    for (row_index, row) in rows.iter_mut().enumerate() {
+        if row.len() <= index {
+            row.resize(index + 1, String::new());
+        }
+        let mut parts: Vec<String> = if row_index == 0 {
+            new_names.to_vec()
+        } else {
+            let parts: Vec<String> = row[index]
+                .splitn(max_splits.saturating_add(1), sub_delimiter)
+                .map(|part| part.trim().to_string())
+                .collect();
+            if parts.len() > 1 {
+                rows_affected += 1;
+            }
+            parts
+        };
+        parts.resize(new_names.len(), String::new());
+        row.splice(index..=index, parts);
+    }
+
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(rows_affected)
+}
+
+// Apply `f` to every data value of `column`. When `f` returns a new value
+// the field is replaced; the number of replaced fields is returned.
+pub(crate) fn map_column<F>(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    mut f: F,
+) -> Result<usize, Box<dyn Error>>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(0);
+    };
+    let index = column.resolve(header)?;
+    let mut replaced = 0;
+
+    for row in rows.iter_mut().skip(1) {
+        let Some(field) = row.get_mut(index) else {
+            continue;
+        };
+        if let Some(value) = f(field) {
+            *field = value;
+            replaced += 1;
+        }
+    }
+
+    if replaced > 0 {
+        ctx.processed_text = write_rows(&rows, delimiter)?;
+    }
+    Ok(replaced)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseTransform {
+    Upper,
+    Lower,
+    // Capitalize the first letter of each whitespace-delimited word
+    TitleCase,
+}
+
+fn title_case(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut at_word_start = true;
+    for c in value.chars() {
+        if c.is_whitespace() {
+            at_word_start = true;
+            out.push(c);
+        } else if at_word_start {
+            at_word_start = false;
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+    out
+}
+
+// Change the case of every value in `column`. Returns the number of fields
+// whose value actually changed.
+pub fn transform_case(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    transform: CaseTransform,
+) -> Result<usize, Box<dyn Error>> {
+    map_column(ctx, delimiter, column, |value| {
+        let transformed = match transform {
+            CaseTransform::Upper => value.to_uppercase(),
+            CaseTransform::Lower => value.to_lowercase(),
+            CaseTransform::TitleCase => title_case(value),
+        };
+        (transformed != value).then_some(transformed)
+    })
+}
+
+// Build a new CSV from the given 0-based data row indices, in the order
+// requested. Out-of-range indices are skipped; the context is not modified.
+pub fn extract_rows(
+    ctx: &CsvContext,
+    delimiter: u8,
+    indices: &[usize],
+    include_header: bool,
+) -> Result<String, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some((header, data)) = rows.split_first() else {
+        return Ok(String::new());
+    };
+
+    let mut selected = Vec::with_capacity(indices.len() + 1);
+    if include_header {
+        selected.push(header.clone());
+    }
+    selected.extend(indices.iter().filter_map(|&i| data.get(i).cloned()));
+    write_rows(&selected, delimiter)
+}
+
+// Collapse the first `header_rows` rows into one header by joining each
+// column's parts with `join_separator`. Empty cells in the upper rows are
+// treated as merged cells and take the value to their left.
+pub fn flatten_headers(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    header_rows: usize,
+    join_separator: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut rows = read_rows(&ctx.processed_text, delimiter)?;
+    let header_rows = header_rows.min(rows.len());
+    if header_rows < 2 {
+        return Ok(());
+    }
+
+    let width = rows[..header_rows].iter().map(Vec::len).max().unwrap_or(0);
+    let mut parts: Vec<Vec<String>> = vec![Vec::new(); width];
+    for (level, row) in rows[..header_rows].iter().enumerate() {
+        let is_last = level + 1 == header_rows;
+        let mut carried = String::new();
+        for (column, column_parts) in parts.iter_mut().enumerate() {
+            let value = row.get(column).map(|v| v.trim()).unwrap_or("");
+            if !value.is_empty() || is_last {
+                carried = value.to_string();
+            }
+            if !carried.is_empty() {
+                column_parts.push(carried.clone());
+            }
+        }
+    }
+
+    let header = parts.iter().map(|p| p.join(join_separator)).collect();
+    rows.splice(..header_rows, [header]);
+    ctx.processed_text = write_rows(&rows, delimiter)?;
+    Ok(())
+}
+
+// Replace empty fields in `columns` with the last non-empty value above them,
+// undoing spreadsheet-style merged cells. Returns the number of fields filled.
+pub fn fill_down(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    columns: &[Column],
+) -> Result<usize, Box<dyn Error>> {
+    let mut filled = 0;
+    for column in columns {
+        let mut last: Option<String> = None;
+        filled += map_column(ctx, delimiter, column, |value| {
+            if value.trim().is_empty() {
+                last.clone()
+            } else {
+                last = Some(value.to_string());
+                None
+            }
+        })?;
+    }
+    Ok(filled)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImputeStrategy {
+    Mean,
+    Median,
+    Mode,
+    Constant(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Imputation {
+    pub imputed: usize,
+    pub value_used: String,
+}
+
+// Median by quickselect. For even counts the lower middle is the largest
+// value left of the selected upper middle.
+fn median(values: &mut [f64]) -> f64 {
+    let (len, mid) = (values.len(), values.len() / 2);
+    let (below, &mut upper, _) = values.select_nth_unstable_by(mid, f64::total_cmp);
+    if len % 2 == 1 {
+        return upper;
+    }
+    let lower = below.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (lower + upper) / 2.0
+}
+
+// Fill null fields in `column` with a value computed from its non-null
+// values. Mean and median only consider numeric values; mode ties go to the
+// value seen first.
+pub fn impute_missing(
+    ctx: &mut CsvContext,
+    delimiter: u8,
+    column: &Column,
+    strategy: &ImputeStrategy,
+) -> Result<Imputation, Box<dyn Error>> {
+    let present: Vec<String> = column_values(ctx, delimiter, column)?
+        .into_iter()
+        .map(|(_, value)| value)
+        .filter(|value| !is_null_value(value, &[]))
+        .collect();
+    let mut numbers: Vec<f64> = present
+        .iter()
+        .filter_map(|value| value.trim().parse::<f64>().ok())
+        .filter(|n| n.is_finite())
+        .collect();
+
+    let value_used = match strategy {
+        ImputeStrategy::Constant(value) => value.clone(),
+        ImputeStrategy::Mean | ImputeStrategy::Median if numbers.is_empty() => {
+            return Err("no numeric values to impute from".into());
+        }
+        ImputeStrategy::Mean => (numbers.iter().sum::<f64>() / numbers.len() as f64).to_string(),
+        ImputeStrategy::Median => median(&mut numbers).to_string(),
+        ImputeStrategy::Mode => {
+            let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+            for (order, value) in present.iter().enumerate() {
+                counts.entry(value).or_insert((0, order)).0 += 1;
+            }
+            let mode = counts
+                .into_iter()
+                .max_by(|(_, (a, a_order)), (_, (b, b_order))| a.cmp(b).then(b_order.cmp(a_order)))
+                .map(|(value, _)| value.to_string());
+            mode.ok_or("no values to impute from")?
+        }
+    };
+
+    let imputed = map_column(ctx, delimiter, column, |value| {
+        is_null_value(value, &[]).then(|| value_used.clone())
+    })?;
+    Ok(Imputation {
+        imputed,
+        value_used,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn concatenate_appends_new_column() {
+        let mut c = ctx("first,last\nAda,Lovelace\n");
+        let options = ConcatenateOptions::default();
+        concatenate_columns(&mut c, b',', &0.into(), &"last".into(), "full", &options).unwrap();
+        assert_eq!(
+            c.processed_text,
+            "first,last,full\nAda,Lovelace,Ada Lovelace\n"
+        );
+    }
+
+    #[test]
+    fn concatenate_drops_originals_and_inserts() {
+        let mut c = ctx("id,first,last\n1,Ada,Lovelace\n");
+        let options = ConcatenateOptions {
+            separator: "_".to_string(),
+            drop_originals: true,
+            insert_at: Some(0),
+        };
+        concatenate_columns(&mut c, b',', &1.into(), &2.into(), "name", &options).unwrap();
+        assert_eq!(c.processed_text, "name,id\nAda_Lovelace,1\n");
+    }
+
+    #[test]
+    fn concatenate_aligns_ragged_rows() {
+        let mut c = ctx("first,last,city\nAda,Lovelace\nAlan,Turing,London,x\n");
+        let options = ConcatenateOptions::default();
+        concatenate_columns(&mut c, b',', &0.into(), &1.into(), "full", &options).unwrap();
+        assert_eq!(
+            c.processed_text,
+            "first,last,city,full\nAda,Lovelace,,Ada Lovelace\nAlan,Turing,London,Alan Turing,x\n"
+        );
+    }
+
+    #[test]
+    fn split_pads_and_truncates() {
+        let mut c = ctx("id,address\n1,\"123 Main St, Springfield, IL\"\n2,Nowhere\n");
+        let names = vec!["street".to_string(), "city".to_string()];
+        let affected = split_column(&mut c, b',', &"address".into(), ",", &names, 5).unwrap();
+        assert_eq!(affected, 1);
+        assert_eq!(
+            c.processed_text,
+            "id,street,city\n1,123 Main St,Springfield\n2,Nowhere,\n"
+        );
+
+        let mut c = ctx("a\nx;y\n");
+        let names = vec!["b".to_string(), "c".to_string()];
+        split_column(&mut c, b',', &0.into(), ";", &names, usize::MAX).unwrap();
+        assert_eq!(c.processed_text, "b,c\nx,y\n");
+    }
+
+    #[test]
+    fn transform_case_is_unicode_aware() {
+        let mut c = ctx("city\nsão paulo\nOSLO\n");
+        let changed = transform_case(&mut c, b',', &0.into(), CaseTransform::TitleCase).unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(c.processed_text, "city\nSão Paulo\nOslo\n");
+
+        transform_case(&mut c, b',', &0.into(), CaseTransform::Upper).unwrap();
+        assert_eq!(c.processed_text, "city\nSÃO PAULO\nOSLO\n");
+
+        // No change keeps the original quoting
+        let mut c = ctx("\"city\"\nOSLO\n");
+        assert_eq!(
+            transform_case(&mut c, b',', &0.into(), CaseTransform::Upper).unwrap(),
+            0
+        );
+        assert_eq!(c.processed_text, "\"city\"\nOSLO\n");
+    }
+
+    #[test]
+    fn extract_rows_skips_out_of_range() {
+        let c = ctx("n\nzero\none\ntwo\n");
+        let out = extract_rows(&c, b',', &[2, 9, 0], true).unwrap();
+        assert_eq!(out, "n\ntwo\nzero\n");
+        assert_eq!(extract_rows(&c, b',', &[1], false).unwrap(), "one\n");
+    }
+
+    #[test]
+    fn flatten_headers_fills_merged_cells() {
+        let mut c = ctx("Sales,,Costs\nQ1,Q2,Q1\n10,20,5\n");
+        flatten_headers(&mut c, b',', 2, "_").unwrap();
+        assert_eq!(c.processed_text, "Sales_Q1,Sales_Q2,Costs_Q1\n10,20,5\n");
+    }
+
+    #[test]
+    fn fill_down_propagates_last_value() {
+        let mut c = ctx("region,city\n,Lyon\nEU,Paris\n,Rome\n\" \",Oslo\n");
+        assert_eq!(fill_down(&mut c, b',', &[0.into()]).unwrap(), 2);
+        assert_eq!(
+            c.processed_text,
+            "region,city\n,Lyon\nEU,Paris\nEU,Rome\nEU,Oslo\n"
+        );
+    }
+
+    #[test]
+    fn missing_values_are_imputed() {
+        let text = "v\n4\n\"\"\n1\n3\n \n10\n";
+        let mut c = ctx(text);
+        let median = impute_missing(&mut c, b',', &0.into(), &ImputeStrategy::Median).unwrap();
+        assert_eq!(median.imputed, 2);
+        assert_eq!(median.value_used, "3.5");
+        assert_eq!(c.processed_text, "v\n4\n3.5\n1\n3\n3.5\n10\n");
+
+        let mut c = ctx(text);
+        let mean = impute_missing(&mut c, b',', &0.into(), &ImputeStrategy::Mean).unwrap();
+        assert_eq!(mean.value_used, "4.5");
+
+        let mut c = ctx("v\nb\na\n\"\"\na\nb\n");
+        let mode = impute_missing(&mut c, b',', &0.into(), &ImputeStrategy::Mode).unwrap();
+        assert_eq!(mode.value_used, "b");
+        assert!(impute_missing(&mut c, b',', &0.into(), &ImputeStrategy::Mean).is_err());
+    }
+}
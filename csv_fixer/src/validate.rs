@@ -0,0 +1,491 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::column::Column;
+use crate::context::CsvContext;
+use crate::csv_io::{read_rows, split_records, trim_terminator};
+
+// Data values of `column` paired with their row index (row 0 is the header).
+// Rows too short to hold the column yield an empty value.
+pub(crate) fn column_values(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<Vec<(usize, String)>, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(Vec::new());
+    };
+    let index = column.resolve(header)?;
+    Ok(rows
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(row_index, row)| (row_index, row.get(index).cloned().unwrap_or_default()))
+        .collect())
+}
+
+// ISO 3166-1 alpha-2 codes, sorted for binary search
+const ISO_COUNTRY_CODES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountryCodeReport {
+    pub valid: usize,
+    pub invalid: usize,
+    pub empty: usize,
+    pub invalid_values: Vec<String>,
+}
+
+// Check `column` against the ISO 3166-1 alpha-2 set, ignoring case
+pub fn validate_iso_country_codes(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<CountryCodeReport, Box<dyn Error>> {
+    let mut report = CountryCodeReport::default();
+    for (_, value) in column_values(ctx, delimiter, column)? {
+        let value = value.trim();
+        if value.is_empty() {
+            report.empty += 1;
+        } else if ISO_COUNTRY_CODES
+            .binary_search(&value.to_ascii_uppercase().as_str())
+            .is_ok()
+        {
+            report.valid += 1;
+        } else {
+            report.invalid += 1;
+            report.invalid_values.push(value.to_string());
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowCountAnomaly {
+    pub actual: usize,
+    pub expected_min: usize,
+    pub expected_max: usize,
+}
+
+impl fmt::Display for RowCountAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row count {} outside expected range {}..={}",
+            self.actual, self.expected_min, self.expected_max
+        )
+    }
+}
+
+impl Error for RowCountAnomaly {}
+
+// Count logical rows (blank lines excluded, quoted line breaks respected) and
+// fail if the count falls outside `[expected_min, expected_max]`
+pub fn detect_line_anomaly(
+    ctx: &CsvContext,
+    expected_min: usize,
+    expected_max: usize,
+) -> Result<usize, RowCountAnomaly> {
+    let actual = split_records(&ctx.processed_text)
+        .into_iter()
+        .filter(|record| !trim_terminator(record).is_empty())
+        .count();
+    if (expected_min..=expected_max).contains(&actual) {
+        Ok(actual)
+    } else {
+        Err(RowCountAnomaly {
+            actual,
+            expected_min,
+            expected_max,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SumCheck {
+    Balanced,
+    Imbalanced { actual_sum: f64, delta: f64 },
+}
+
+// Assert that the numeric values of `column` add up to `expected_sum` within
+// `tolerance`. Empty, non-numeric, and NaN or infinite fields are skipped.
+pub fn validate_column_sum(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    expected_sum: f64,
+    tolerance: f64,
+) -> Result<SumCheck, Box<dyn Error>> {
+    let actual_sum: f64 = column_values(ctx, delimiter, column)?
+        .iter()
+        .filter_map(|(_, value)| value.trim().parse::<f64>().ok())
+        .filter(|n| n.is_finite())
+        .sum();
+    let delta = actual_sum - expected_sum;
+    if delta.abs() > tolerance {
+        Ok(SumCheck::Imbalanced { actual_sum, delta })
+    } else {
+        Ok(SumCheck::Balanced)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uniqueness {
+    Unique,
+    // Each duplicated value with every row index it appears on
+    Duplicates(Vec<(String, Vec<usize>)>),
+}
+
+// Check a single column for duplicate values. The common case of a unique
+// column only needs one pass over a `HashSet<&str>`.
+pub fn validate_unique(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+) -> Result<Uniqueness, Box<dyn Error>> {
+    let values = column_values(ctx, delimiter, column)?;
+    let mut seen: HashSet<&str> = HashSet::with_capacity(values.len());
+    let duplicated: HashSet<&str> = values
+        .iter()
+        .map(|(_, value)| value.as_str())
+        .filter(|value| !seen.insert(value))
+        .collect();
+    if duplicated.is_empty() {
+        return Ok(Uniqueness::Unique);
+    }
+
+    // Index into `duplicates` keeps groups in first-seen order
+    let mut positions: HashMap<&str, usize> = HashMap::with_capacity(duplicated.len());
+    let mut duplicates: Vec<(String, Vec<usize>)> = Vec::with_capacity(duplicated.len());
+    for (row_index, value) in &values {
+        if !duplicated.contains(value.as_str()) {
+            continue;
+        }
+        match positions.get(value.as_str()) {
+            Some(&position) => duplicates[position].1.push(*row_index),
+            None => {
+                positions.insert(value, duplicates.len());
+                duplicates.push((value.clone(), vec![*row_index]));
+            }
+        }
+    }
+    Ok(Uniqueness::Duplicates(duplicates))
+}
+
+// Empty, whitespace-only, or one of `null_tokens` (compared case-insensitively)
+pub(crate) fn is_null_value(value: &str, null_tokens: &[&str]) -> bool {
+    let value = value.trim();
+    value.is_empty() || null_tokens.iter().any(|t| value.eq_ignore_ascii_case(t))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotNullCheck {
+    AllPresent,
+    // Column name with the row indices holding nulls
+    NullFound(Vec<(String, Vec<usize>)>),
+}
+
+// Verify that every `required_columns` value is present. Empty fields,
+// whitespace-only fields, and `null_tokens` such as "N/A" all count as null.
+pub fn validate_not_null(
+    ctx: &CsvContext,
+    delimiter: u8,
+    required_columns: &[Column],
+    null_tokens: &[&str],
+) -> Result<NotNullCheck, Box<dyn Error>> {
+    let rows = read_rows(&ctx.processed_text, delimiter)?;
+    let Some(header) = rows.first() else {
+        return Ok(NotNullCheck::AllPresent);
+    };
+
+    let mut found = Vec::new();
+    for column in required_columns {
+        let index = column.resolve(header)?;
+        let null_rows: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, row)| is_null_value(row.get(index).map_or("", |v| v), null_tokens))
+            .map(|(row_index, _)| row_index)
+            .collect();
+        if !null_rows.is_empty() {
+            found.push((header[index].clone(), null_rows));
+        }
+    }
+
+    if found.is_empty() {
+        Ok(NotNullCheck::AllPresent)
+    } else {
+        Ok(NotNullCheck::NullFound(found))
+    }
+}
+
+// Parse `processed_text` with the csv crate as a ground truth. Every record
+// must parse and match the header's field count. Returns the number of data
+// rows, or each failure as (row, message).
+pub fn validate_csv_parse(ctx: &CsvContext, delimiter: u8) -> Result<usize, Vec<(usize, String)>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(ctx.processed_text.as_bytes());
+
+    let mut row_count = 0;
+    let mut violations = Vec::new();
+    for (index, result) in rdr.records().enumerate() {
+        match result {
+            Ok(_) => row_count += 1,
+            Err(err) => {
+                // Data records start on row 1; row 0 is the header
+                let row = err.position().map_or(index + 1, |p| p.record() as usize);
+                violations.push((row, err.to_string()));
+            }
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(row_count)
+    } else {
+        Err(violations)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegerRangeReport {
+    // (row, value) for integers outside the range
+    pub violations: Vec<(usize, i64)>,
+    // Non-empty fields that are not integers
+    pub parse_failures: usize,
+}
+
+// Collect integers in `column` that fall outside `[min, max]`
+pub fn validate_integer_range(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    min: i64,
+    max: i64,
+) -> Result<IntegerRangeReport, Box<dyn Error>> {
+    let mut report = IntegerRangeReport::default();
+    for (row, value) in column_values(ctx, delimiter, column)? {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match value.parse::<i64>() {
+            Ok(n) if !(min..=max).contains(&n) => report.violations.push((row, n)),
+            Ok(_) => {}
+            Err(_) => report.parse_failures += 1,
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FloatRangeReport {
+    // (row, value) for floats outside the range or disallowed NaN/infinities
+    pub violations: Vec<(usize, f64)>,
+    // Non-empty fields that are not numbers
+    pub parse_failures: usize,
+}
+
+// Bounds check for float columns. NaN and infinities are violations unless
+// explicitly allowed; allowed infinities skip the range check.
+pub fn validate_float_range(
+    ctx: &CsvContext,
+    delimiter: u8,
+    column: &Column,
+    min: f64,
+    max: f64,
+    allow_nan: bool,
+    allow_inf: bool,
+) -> Result<FloatRangeReport, Box<dyn Error>> {
+    let mut report = FloatRangeReport::default();
+    for (row, value) in column_values(ctx, delimiter, column)? {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let Ok(n) = value.parse::<f64>() else {
+            report.parse_failures += 1;
+            continue;
+        };
+        let violates = if n.is_nan() {
+            !allow_nan
+        } else if n.is_infinite() {
+            !allow_inf
+        } else {
+            n < min || n > max
+        };
+        if violates {
+            report.violations.push((row, n));
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnConsistency {
+    Consistent(usize),
+    // (row_index, expected, actual); row 0 is the header after skipping
+    Inconsistent(Vec<(usize, usize, usize)>),
+}
+
+// Skip `skip_rows` leading records (titles, metadata), take the next row as
+// the header, and check that every later row has the same field count
+pub fn validate_column_consistency(
+    ctx: &CsvContext,
+    delimiter: u8,
+    skip_rows: usize,
+) -> Result<ColumnConsistency, Box<dyn Error>> {
+    let records = split_records(&ctx.processed_text);
+    let remainder = records[skip_rows.min(records.len())..].concat();
+    let rows = read_rows(&remainder, delimiter)?;
+    let Some(expected) = rows.first().map(Vec::len) else {
+        return Ok(ColumnConsistency::Consistent(0));
+    };
+
+    let mismatches: Vec<(usize, usize, usize)> = rows
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, row)| row.len() != expected)
+        .map(|(row_index, row)| (row_index, expected, row.len()))
+        .collect();
+    if mismatches.is_empty() {
+        Ok(ColumnConsistency::Consistent(expected))
+    } else {
+        Ok(ColumnConsistency::Inconsistent(mismatches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(text: &str) -> CsvContext {
+        CsvContext::new(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn country_codes_are_case_insensitive() {
+        let c = ctx("country\nus\nDE\nXX\n\"\"\n");
+        let report = validate_iso_country_codes(&c, b',', &0.into()).unwrap();
+        assert_eq!(report.valid, 2);
+        assert_eq!(report.invalid, 1);
+        assert_eq!(report.empty, 1);
+        assert_eq!(report.invalid_values, vec!["XX".to_string()]);
+    }
+
+    #[test]
+    fn line_anomaly_counts_logical_rows() {
+        let c = ctx("a\n\"multi\nline\"\n\nb\n");
+        assert_eq!(detect_line_anomaly(&c, 1, 5), Ok(3));
+        assert_eq!(
+            detect_line_anomaly(&c, 10, 20),
+            Err(RowCountAnomaly {
+                actual: 3,
+                expected_min: 10,
+                expected_max: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn column_sum_is_checked_within_tolerance() {
+        let c = ctx("amount\n10.50\n\n4.25\nn/a\nNaN\ninf\n");
+        let check = validate_column_sum(&c, b',', &"amount".into(), 14.75, 0.001).unwrap();
+        assert_eq!(check, SumCheck::Balanced);
+        let check = validate_column_sum(&c, b',', &0.into(), 20.0, 0.01).unwrap();
+        assert_eq!(
+            check,
+            SumCheck::Imbalanced {
+                actual_sum: 14.75,
+                delta: -5.25,
+            }
+        );
+    }
+
+    #[test]
+    fn duplicates_are_grouped_with_rows() {
+        let c = ctx("id\na\nb\na\nc\na\n");
+        assert_eq!(
+            validate_unique(&c, b',', &0.into()).unwrap(),
+            Uniqueness::Duplicates(vec![("a".to_string(), vec![1, 3, 5])])
+        );
+        let unique = ctx("id\n1\n2\n");
+        assert_eq!(
+            validate_unique(&unique, b',', &0.into()).unwrap(),
+            Uniqueness::Unique
+        );
+    }
+
+    #[test]
+    fn nulls_include_whitespace_and_tokens() {
+        let c = ctx("id,name\n1,Ada\n2,  \n3,n/a\n,Bob\n");
+        let check = validate_not_null(&c, b',', &[0.into(), "name".into()], &["N/A"]).unwrap();
+        assert_eq!(
+            check,
+            NotNullCheck::NullFound(vec![
+                ("id".to_string(), vec![4]),
+                ("name".to_string(), vec![2, 3]),
+            ])
+        );
+    }
+
+    #[test]
+    fn csv_parse_reports_ragged_rows() {
+        assert_eq!(validate_csv_parse(&ctx("a,b\n1,2\n3,4\n"), b','), Ok(2));
+        let violations = validate_csv_parse(&ctx("a,b\n1,2\n3\n4,5\n"), b',').unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].0, 2);
+    }
+
+    #[test]
+    fn integer_range_separates_parse_failures() {
+        let c = ctx("age\n34\n-1\n\"\"\nold\n130\n");
+        let report = validate_integer_range(&c, b',', &0.into(), 0, 120).unwrap();
+        assert_eq!(report.violations, vec![(2, -1), (5, 130)]);
+        assert_eq!(report.parse_failures, 1);
+    }
+
+    #[test]
+    fn float_range_handles_special_values() {
+        let c = ctx("x\n0.5\n1.5\nNaN\ninf\nabc\n\"\"\n");
+        let strict = validate_float_range(&c, b',', &0.into(), 0.0, 1.0, false, false).unwrap();
+        assert_eq!(strict.violations.len(), 3);
+        assert_eq!(strict.violations[0], (2, 1.5));
+        assert_eq!(strict.parse_failures, 1);
+        let lenient = validate_float_range(&c, b',', &0.into(), 0.0, 1.0, true, true).unwrap();
+        assert_eq!(lenient.violations, vec![(2, 1.5)]);
+    }
+
+    #[test]
+    fn column_consistency_skips_metadata_rows() {
+        let c = ctx("Report\nGenerated today\na,b\n1,2\n3,4\n");
+        assert_eq!(
+            validate_column_consistency(&c, b',', 2).unwrap(),
+            ColumnConsistency::Consistent(2)
+        );
+        assert_eq!(
+            validate_column_consistency(&c, b',', 1).unwrap(),
+            ColumnConsistency::Inconsistent(vec![(1, 1, 2), (2, 1, 2), (3, 1, 2)])
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use walkdir::WalkDir;
+
+use crate::context::CsvContext;
+use crate::repair::{RepairReport, repair};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub fn index_files<P: AsRef<Path>>(start_dir: P) -> io::Result<Vec<PathBuf>> {
+    // Create a container for data
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for entry_result in WalkDir::new(start_dir) {
+        let entry = match entry_result {
+            Ok(entry) => entry,
+            Err(err) => {
+                eprintln!("Error reading directory entry: {err}");
+                continue; // Skip problematic entries
+            }
+        };
+
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                eprintln!("Error getting metadata for entry: {err}");
+                continue;
+            }
+        };
+
+        if metadata.is_file() {
+            let file_path = entry.path().to_path_buf();
+            files.push(file_path);
+        }
+    }
+    Ok(files)
+}
+
+// Size and modification time, compared between polls to spot changes
+type FileStamp = (u64, Option<SystemTime>);
+
+fn csv_files(dir: &Path) -> HashMap<PathBuf, FileStamp> {
+    index_files(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        })
+        .filter_map(|path| {
+            let stamp = file_stamp(&path)?;
+            Some((path, stamp))
+        })
+        .collect()
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
+
+// Polling state for one directory. A `.csv` file is repaired once its size
+// and mtime are the same on two consecutive polls, so files still being
+// written are left alone, and again whenever it changes afterwards. Files
+// present when the watcher is created are not repaired until they change.
+pub struct DirectoryWatcher {
+    dir: PathBuf,
+    // Stamps seen on the previous poll
+    previous: HashMap<PathBuf, FileStamp>,
+    // Stamps of files as they were after their last repair
+    handled: HashMap<PathBuf, FileStamp>,
+}
+
+impl DirectoryWatcher {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        let previous = csv_files(&dir);
+        Self {
+            dir,
+            handled: previous.clone(),
+            previous,
+        }
+    }
+
+    // Scan the directory once, calling `callback` with the path, repaired
+    // text and report of each file that is due. Returns the files repaired.
+    pub fn poll<F>(&mut self, mut callback: F) -> usize
+    where
+        F: FnMut(PathBuf, String, RepairReport),
+    {
+        let current = csv_files(&self.dir);
+        self.handled.retain(|path, _| current.contains_key(path));
+        let mut repaired = 0;
+        for (path, stamp) in &current {
+            let stable = self.previous.get(path) == Some(stamp);
+            if !stable || self.handled.get(path) == Some(stamp) {
+                continue;
+            }
+            match CsvContext::from_path(path) {
+                Ok(mut ctx) => {
+                    let report = repair(&mut ctx);
+                    callback(path.clone(), ctx.processed_text, report);
+                    repaired += 1;
+                }
+                Err(err) => eprintln!("Error reading {}: {err}", path.display()),
+            }
+            // Re-read so a callback writing the repaired text back does not
+            // trigger another repair
+            let after = file_stamp(path).unwrap_or(*stamp);
+            self.handled.insert(path.clone(), after);
+        }
+        self.previous = current;
+        repaired
+    }
+}
+
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+// Run a `DirectoryWatcher` over `dir` on a background thread, polling every
+// `POLL_INTERVAL` until the handle is stopped.
+pub fn watch_directory<F>(dir: &str, mut callback: F) -> WatchHandle
+where
+    F: FnMut(PathBuf, String, RepairReport) + Send + 'static,
+{
+    let mut watcher = DirectoryWatcher::new(dir);
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::clone(&stop);
+
+    let worker = thread::spawn(move || {
+        while !stop_flag.load(Ordering::Relaxed) {
+            thread::sleep(POLL_INTERVAL);
+            watcher.poll(&mut callback);
+        }
+    });
+
+    WatchHandle {
+        stop,
+        worker: Some(worker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_files_are_repaired_once_stable() {
+        let dir = std::env::temp_dir().join(format!("csv_fixer_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("existing.csv"), "a\n").unwrap();
+
+        let mut watcher = DirectoryWatcher::new(&dir);
+        let mut seen = Vec::new();
+        let mut record = |path: PathBuf, text: String, report: RepairReport| {
+            seen.push((
+                path.file_name().unwrap().to_owned(),
+                text,
+                report.events.len(),
+            ));
+        };
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        fs::write(dir.join("new.csv"), "a\n\"1\r\n2\"\n").unwrap();
+
+        // First sighting only records the size and mtime
+        assert_eq!(watcher.poll(&mut record), 0);
+        assert_eq!(watcher.poll(&mut record), 1);
+        assert_eq!(watcher.poll(&mut record), 0);
+
+        // A changed file is repaired again once it settles
+        fs::write(dir.join("existing.csv"), "a\n1\n").unwrap();
+        assert_eq!(watcher.poll(&mut record), 0);
+        assert_eq!(watcher.poll(&mut record), 1);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            seen,
+            vec![
+                ("new.csv".into(), "a\n\"1 2\"\n".to_string(), 1),
+                ("existing.csv".into(), "a\n1\n".to_string(), 0),
+            ]
+        );
+    }
+}